@@ -0,0 +1,26 @@
+//! Measures the time to enumerate all subsets of a 20-element dataset. Run with
+//! `cargo bench`.
+use std::time::Instant;
+use subset_generator::SubsetGenerator;
+
+fn main() {
+    let data: Vec<u64> = (0..20).collect();
+    let sg = SubsetGenerator::new(&data, true);
+
+    let start = Instant::now();
+    let mut iter = sg.iter();
+    let mut buf = Vec::with_capacity(data.len());
+    let mut total = 0;
+    while iter.next_into(&mut buf) {
+        total += buf.len();
+    }
+    println!("next_into: {:?} ({} elements)", start.elapsed(), total);
+
+    let start = Instant::now();
+    let total: usize = sg.iter().map(|subset| subset.len()).sum();
+    println!("next:      {:?} ({} elements)", start.elapsed(), total);
+
+    let start = Instant::now();
+    let total: usize = sg.lazy_iter().map(|elements| elements.len()).sum();
+    println!("lazy:      {:?} ({} elements)", start.elapsed(), total);
+}
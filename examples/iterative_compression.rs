@@ -0,0 +1,35 @@
+use subset_generator::SubsetGenerator;
+
+/// Returns true if `cover` covers all the edges between the first `n` vertices.
+fn is_cover(edges: &[(usize, usize)], n: usize, cover: &[&usize]) -> bool {
+    edges
+        .iter()
+        .filter(|&&(u, v)| u < n && v < n)
+        .all(|(u, v)| cover.contains(&u) || cover.contains(&v))
+}
+
+fn main() {
+    // The Petersen graph, which has a minimum vertex cover of size 6. The
+    // rows are the outer cycle, the spokes and the inner pentagram, a layout
+    // that rustfmt would flatten into one edge per line.
+    #[rustfmt::skip]
+    let edges = [
+        (0, 1), (1, 2), (2, 3), (3, 4), (4, 0),
+        (0, 5), (1, 6), (2, 7), (3, 8), (4, 9),
+        (5, 7), (7, 9), (9, 6), (6, 8), (8, 5),
+    ];
+    let vertices: Vec<usize> = (0..10).collect();
+
+    // Add the vertices one by one. The cover of the previous graph plus the
+    // new vertex covers the current graph, and is compressed while possible.
+    let mut cover: Vec<usize> = vec![];
+    for n in 1..=vertices.len() {
+        cover.push(n - 1);
+        let sg = SubsetGenerator::new(&vertices[..n], false);
+        while let Some(smaller) = sg.compress(&cover, |s| is_cover(&edges, n, s)) {
+            cover = smaller.into_iter().copied().collect();
+        }
+    }
+
+    println!("minimum vertex cover: {:?}", cover);
+}
@@ -0,0 +1,13 @@
+use subset_generator::SubsetGenerator;
+
+fn main() {
+    let set = [3, 34, 4, 12, 5, 2];
+    let target = 9;
+
+    // Each subset is an iterator over its elements, so summing them allocates
+    // nothing, unlike collecting every subset into a vector first.
+    let sg = SubsetGenerator::new(&set, false);
+    let sums = sg.lazy_iter().map(|elements| elements.sum::<i32>());
+    let hits = sums.filter(|&sum| sum == target).count();
+    println!("{} subsets sum to {}", hits, target);
+}
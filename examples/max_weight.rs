@@ -0,0 +1,48 @@
+use subset_generator::SubsetGenerator;
+
+/// The total weight and value of a packing, maintained as items are added and
+/// removed.
+#[derive(Clone, Copy, Debug, Default)]
+struct Packing {
+    weight: u32,
+    value: u32,
+}
+
+fn main() {
+    // (weight, value) of every item.
+    let items = [
+        (23, 92),
+        (31, 57),
+        (29, 49),
+        (44, 68),
+        (53, 60),
+        (38, 43),
+        (63, 67),
+        (85, 84),
+        (89, 87),
+        (82, 72),
+    ];
+    let capacity = 165;
+
+    // Every step of the Gray-code order adds or removes a single item, so the
+    // totals are updated in constant time instead of summed for every subset.
+    let sg = SubsetGenerator::new(&items, true);
+    let (best, mask) = sg.optimize_incremental(
+        Packing::default(),
+        |p, &(weight, value)| {
+            p.weight += weight;
+            p.value += value;
+        },
+        |p, &(weight, value)| {
+            p.weight -= weight;
+            p.value -= value;
+        },
+        |a, b| a.weight <= capacity && a.value > b.value,
+    );
+
+    let chosen: Vec<usize> = (0..items.len()).filter(|&i| mask[i]).collect();
+    println!(
+        "items {:?} weigh {} and are worth {}",
+        chosen, best.weight, best.value
+    );
+}
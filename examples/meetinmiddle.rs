@@ -0,0 +1,19 @@
+use subset_generator::SubsetGenerator;
+
+fn main() {
+    let set = [
+        267, 493, 869, 961, 1000, 1153, 1246, 1598, 1766, 1922, 2107, 2328, 2481, 2707, 2916, 3096,
+        3305, 3594, 3806, 4008, 4242, 4397, 4591, 4861, 5063, 5280, 5479, 5735, 5924, 6131,
+    ];
+    let target = 50_000;
+
+    // 2^30 subsets are too many to enumerate, but each half only has 2^15.
+    let sg = SubsetGenerator::new(&set, false);
+    let sum = |subset: &[&i64]| subset.iter().copied().sum::<i64>();
+    let found = sg.meet_in_middle(sum, |a, b| (a + b).cmp(&target));
+
+    match found {
+        Some(subset) => println!("{:?} sums to {}", subset, sum(&subset)),
+        None => println!("no subset sums to {}", target),
+    }
+}
@@ -11,7 +11,7 @@ fn main() {
         vec![0, 3, 4],
     ];
 
-    let mut opt = std::usize::MAX;
+    let mut opt = usize::MAX;
 
     let sg = SubsetGenerator::new(&families, false);
     for subset in sg.iter() {
@@ -1,18 +1,12 @@
 use subset_generator::SubsetGenerator;
 
 fn main() {
-    let set = vec![3, 34, 4, 12, 5, 2];
+    let set = [3, 34, 4, 12, 5, 2];
     let target = 9;
 
     let sg = SubsetGenerator::new(&set, false);
-    let mut found = false;
-    for subset in sg.into_iter() {
-        let sum = subset.into_iter().fold(0, |acc, i| acc + *i);
-        if sum == target {
-            found = true;
-            break;
-        }
+    match sg.subset_sum(target, |&x| x) {
+        Some(subset) => println!("{:?} sums to {}", subset, target),
+        None => println!("no subset sums to {}", target),
     }
-
-    println!("{:}", found);
-}
\ No newline at end of file
+}
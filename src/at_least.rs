@@ -0,0 +1,117 @@
+use crate::{SubsetGenerator, SubsetIter};
+use alloc::vec::Vec;
+
+/// Iterator over the subsets with at least a given number of elements, created
+/// by [`SubsetGenerator::at_least`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2, 3, 4];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// assert_eq!(sg.at_least(3).count(), 5);
+/// ```
+pub struct AtLeastIter<'a, T> {
+    iter: SubsetIter<'a, T>,
+    k: usize,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over all the subsets with at least `k` elements, in
+    /// the same order as `iter` reports them. Rather than filtering the whole
+    /// powerset, the cursor jumps from a mask directly to the next mask with
+    /// enough set bits, by setting its lowest clear bits, so masks that cannot
+    /// reach the threshold are never visited. For `k == 0` this is the full
+    /// enumeration, including the empty set if (and only if) the generator was
+    /// configured to include it, and for `k > n` the iterator yields nothing.
+    /// The constraints of the generator are respected, and `k` counts the
+    /// required elements too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.at_least(2);
+    ///
+    /// assert_eq!(iter.next(), Some(vec![&1, &2]));
+    /// assert_eq!(iter.next(), Some(vec![&1, &3]));
+    /// assert_eq!(iter.next(), Some(vec![&2, &3]));
+    /// assert_eq!(iter.next(), Some(vec![&1, &2, &3]));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn at_least(&self, k: usize) -> AtLeastIter<'a, T> {
+        // The cursor only counts the free elements.
+        let required = self.required_len();
+        let k = k.saturating_sub(required);
+        let mut iter = self.iter();
+        iter.with_emptyset &= k == 0;
+        iter.exhausted = k > self.free_len();
+        AtLeastIter { iter, k }
+    }
+}
+
+impl<'a, T> Iterator for AtLeastIter<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let iter = &mut self.iter;
+        let mut subset = Vec::new();
+        if iter.with_emptyset {
+            iter.with_emptyset = false;
+            iter.select_into(None, &mut subset);
+            return Some(subset);
+        }
+        if iter.exhausted || !iter.set.increment() {
+            iter.exhausted = true;
+            return None;
+        }
+
+        // The smallest mask above the cursor with at least `k` set bits is the
+        // incremented cursor with its lowest missing bits set.
+        let ones = iter.set.count_ones();
+        if ones < self.k {
+            iter.set.fill_lowest_zeros(self.k - ones);
+        }
+        iter.select_into(Some(&iter.set), &mut subset);
+        Some(subset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+
+    #[test]
+    fn at_least_matches_filter() {
+        let data: Vec<usize> = (0..8).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            for k in 0..=9 {
+                let expected: Vec<_> = generator.iter().filter(|s| s.len() >= k).collect();
+                let actual: Vec<_> = generator.at_least(k).collect();
+                assert_eq!(actual, expected, "k = {}", k);
+            }
+        }
+    }
+
+    #[test]
+    fn at_least_large_set() {
+        let data: Vec<usize> = (0..70).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        assert_eq!(generator.at_least(69).count(), 71);
+        assert_eq!(generator.at_least(70).next().unwrap().len(), 70);
+        assert_eq!(generator.at_least(71).next(), None);
+    }
+
+    #[test]
+    fn at_least_empty_dataset() {
+        let data: Vec<u32> = vec![];
+        assert_eq!(SubsetGenerator::new(&data, true).at_least(0).count(), 1);
+        assert_eq!(SubsetGenerator::new(&data, false).at_least(0).count(), 0);
+    }
+}
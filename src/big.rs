@@ -0,0 +1,146 @@
+use crate::mask::Mask;
+use crate::rank::unrank_big;
+use crate::{SubsetGenerator, SubsetIter};
+use alloc::vec::Vec;
+use num_bigint::BigUint;
+
+impl<'a, T> SubsetIter<'a, T> {
+    /// Moves the cursor to the subset with the given index, like
+    /// [`seek`](Self::seek), but with a `BigUint` index so that it works for
+    /// datasets of any size. Returns `None`, and leaves the iterator untouched,
+    /// if `index >= 2^n`. Only available with the `big` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data: Vec<u32> = (0..80).collect();
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.iter();
+    ///
+    /// let index = (BigUint::from(1u8) << 79) + 1u8;
+    /// assert_eq!(iter.seek_big(&index), Some(vec![&0, &79]));
+    /// assert_eq!(iter.next(), Some(vec![&1, &79]));
+    /// ```
+    pub fn seek_big(&mut self, index: &BigUint) -> Option<Vec<&'a T>> {
+        let len = self.set.len();
+        if index.bits() > len as u64 {
+            return None;
+        }
+        self.seek_mask(Mask::from_bitvec(unrank_big(index, len)))
+    }
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Splits the enumeration into `chunks` iterators over contiguous ranges
+    /// of the subset indices, like [`split`](Self::split), but computing the
+    /// ranges with `BigUint` indices so that it works for datasets of any size.
+    /// Only available with the `big` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunks == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data: Vec<u32> = (0..80).collect();
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut chunks = sg.split_big(4);
+    ///
+    /// assert_eq!(chunks[1].next(), Some(vec![&78]));
+    /// assert_eq!(chunks[3].next_back(), Some(data.iter().collect()));
+    /// ```
+    pub fn split_big(&self, chunks: usize) -> Vec<SubsetIter<'a, T>> {
+        assert!(chunks > 0, "cannot split the subsets into zero chunks");
+        let len = self.free_len();
+        let total = BigUint::from(1u8) << len;
+        let one = BigUint::from(1u8);
+
+        (0..chunks)
+            .map(|i| {
+                let start = &total * i / chunks;
+                let end = &total * (i + 1) / chunks;
+                // The front cursor holds the last reported mask, and the back
+                // cursor the last mask to report.
+                let empty = start >= end;
+                let first = if start.bits() == 0 {
+                    start.clone()
+                } else {
+                    &start - &one
+                };
+                let last = if empty { first.clone() } else { &end - &one };
+                self.range_of(
+                    Mask::from_bitvec(unrank_big(&first, len)),
+                    Mask::from_bitvec(unrank_big(&last, len)),
+                    start.bits() == 0 && !empty,
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn split_big_matches_split() {
+        let data: Vec<usize> = (0..7).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            for chunks in 1..20 {
+                let expected: Vec<_> = generator
+                    .split(chunks)
+                    .into_iter()
+                    .map(|c| c.collect::<Vec<_>>())
+                    .collect();
+                let actual: Vec<_> = generator
+                    .split_big(chunks)
+                    .into_iter()
+                    .map(|c| c.collect::<Vec<_>>())
+                    .collect();
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn split_big_large_set() {
+        let data: Vec<usize> = (0..80).collect();
+        let generator = SubsetGenerator::new(&data, true);
+        let mut chunks = generator.split_big(3);
+        assert_eq!(chunks[0].next(), Some(vec![]));
+        for pair in chunks.windows(2) {
+            let (mut front, mut back) = (pair[0].clone(), pair[1].clone());
+            let last = front.next_back().unwrap();
+            let first = back.next().unwrap();
+            assert_eq!(
+                crate::rank::rank_big(&mask_of(&first)),
+                crate::rank::rank_big(&mask_of(&last)) + 1u8
+            );
+        }
+    }
+
+    fn mask_of(subset: &[&usize]) -> bit_vec::BitVec {
+        bit_vec::BitVec::from_fn(80, |i| subset.contains(&&i))
+    }
+
+    #[test]
+    fn seek_big_matches_seek() {
+        let data: Vec<usize> = (0..10).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        for index in 0..1024u32 {
+            let mut a = generator.iter();
+            let mut b = generator.iter();
+            assert_eq!(a.seek_big(&BigUint::from(index)), b.seek(index as u64));
+            assert!(a.eq(b));
+        }
+        assert_eq!(generator.iter().seek_big(&BigUint::from(1024u32)), None);
+    }
+}
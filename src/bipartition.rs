@@ -0,0 +1,142 @@
+use crate::{SubsetGenerator, SubsetIter};
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// Iterator over the ways to split the dataset into two labeled groups,
+/// created by [`SubsetGenerator::bipartitions`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// assert_eq!(sg.bipartitions(false).count(), 8);
+/// assert_eq!(sg.bipartitions(true).count(), 4);
+/// ```
+pub struct BipartitionIter<'a, T> {
+    iter: SubsetIter<'a, T>,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over all the assignments of the elements to two
+    /// groups `A` and `B`, such that every element is in exactly one of them,
+    /// as needed for 2-colorings and balanced partitions. This is the same
+    /// enumeration as [`with_complement`](Self::with_complement), except that
+    /// every assignment is reported: either group may be empty, whether or
+    /// not the generator includes the empty set. Required elements are always
+    /// in `A`, and forbidden elements are always in `B`.
+    ///
+    /// If `dedup_symmetric` is set, the first free element is fixed in `A`, so
+    /// a partition and its mirror image with `A` and `B` swapped are reported
+    /// only once, which halves the output. With constraints, the two groups are
+    /// not interchangeable, and this just halves the output. Both groups list
+    /// their elements in the order of the dataset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.bipartitions(false);
+    ///
+    /// assert_eq!(iter.next(), Some((vec![], vec![&1, &2])));
+    /// assert_eq!(iter.next(), Some((vec![&1], vec![&2])));
+    /// assert_eq!(iter.next(), Some((vec![&2], vec![&1])));
+    /// assert_eq!(iter.next(), Some((vec![&1, &2], vec![])));
+    ///
+    /// let halves: Vec<_> = sg.bipartitions(true).collect();
+    /// assert_eq!(halves, vec![(vec![&1], vec![&2]), (vec![&1, &2], vec![])]);
+    /// ```
+    pub fn bipartitions(&self, dedup_symmetric: bool) -> BipartitionIter<'a, T> {
+        let mut generator = self.clone();
+        generator.with_emptyset = true;
+        if dedup_symmetric && generator.free_len() > 0 {
+            let first = match &generator.constraints {
+                Some(constraints) => constraints.position(0),
+                None => 0,
+            };
+            generator = generator.with_required(&[first]);
+        }
+        BipartitionIter {
+            iter: generator.iter(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for BipartitionIter<'a, T> {
+    type Item = (Vec<&'a T>, Vec<&'a T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The pending base subset is reported before the cursor moves.
+        let base = self.iter.with_emptyset;
+        let group = self.iter.next()?;
+        let mut other = Vec::with_capacity(self.iter.data.len() - group.len());
+        let mask = if base { None } else { Some(&self.iter.set) };
+        self.iter.complement_into(mask, &mut other);
+        Some((group, other))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for BipartitionIter<'a, T> {}
+
+impl<'a, T> FusedIterator for BipartitionIter<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+    use std::collections::HashSet;
+
+    #[test]
+    fn bipartitions_cover_assignments() {
+        let data: Vec<usize> = (0..6).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let all: Vec<_> = generator.bipartitions(false).collect();
+            assert_eq!(all.len(), 64);
+            assert_eq!(generator.bipartitions(false).len(), 64);
+            let mut seen = HashSet::new();
+            for (a, b) in &all {
+                assert_eq!(a.len() + b.len(), data.len());
+                assert!(a.iter().all(|x| !b.contains(x)));
+                assert!(seen.insert(a.clone()));
+            }
+
+            let halves: Vec<_> = generator.bipartitions(true).collect();
+            assert_eq!(halves.len(), 32);
+            for (a, b) in &halves {
+                assert!(a.contains(&&0));
+                assert!(!halves.contains(&(b.clone(), a.clone())));
+                assert!(all.contains(&(a.clone(), b.clone())));
+            }
+        }
+
+        let empty: Vec<usize> = vec![];
+        let generator = SubsetGenerator::new(&empty, false);
+        assert_eq!(
+            generator.bipartitions(true).collect::<Vec<_>>(),
+            vec![(vec![], vec![])]
+        );
+    }
+
+    #[test]
+    fn bipartitions_with_constraints() {
+        let data: Vec<usize> = (0..5).collect();
+        let generator = SubsetGenerator::new(&data, false)
+            .with_required(&[2])
+            .with_forbidden(&[0]);
+        assert_eq!(generator.bipartitions(false).count(), 8);
+        let halves: Vec<_> = generator.bipartitions(true).collect();
+        assert_eq!(halves.len(), 4);
+        for (a, b) in halves {
+            assert!(a.contains(&&1) && a.contains(&&2) && b.contains(&&0));
+        }
+    }
+}
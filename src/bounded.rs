@@ -0,0 +1,117 @@
+use crate::constraints::FreeElements;
+use crate::{KSubsetIter, SubsetGenerator};
+use alloc::vec::Vec;
+
+/// Iterator over the subsets whose number of elements lies in a range, created
+/// by [`SubsetGenerator::bounded`]. The subsets are reported in nondecreasing
+/// order of cardinality.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2, 3, 4];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// assert_eq!(sg.bounded(1, 2).count(), 10);
+/// ```
+pub struct BoundedSubsetIter<'a, T> {
+    elements: FreeElements<'a, T>,
+    size: usize,
+    max: usize,
+    layer: KSubsetIter<'a, T>,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over all the subsets with at least `min` and at most
+    /// `max` elements. All subsets of size `min` are reported first, then all
+    /// subsets of size `min + 1`, and so on, so the enumeration can be stopped
+    /// as soon as a solution of the smallest size is found. A `max` of at least
+    /// `n` is clamped to `n`, and if `min > max` the iterator yields nothing.
+    /// The empty set is reported if (and only if) `min == 0`, regardless of
+    /// whether the generator was configured to include it. The constraints of
+    /// the generator are respected as by `combinations`, so the sizes count
+    /// the required elements too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.bounded(1, 2);
+    ///
+    /// assert_eq!(iter.next(), Some(vec![&1]));
+    /// assert_eq!(iter.next(), Some(vec![&2]));
+    /// assert_eq!(iter.next(), Some(vec![&3]));
+    /// assert_eq!(iter.next(), Some(vec![&1, &2]));
+    /// assert_eq!(iter.count(), 2);
+    /// ```
+    pub fn bounded(&self, min: usize, max: usize) -> BoundedSubsetIter<'a, T> {
+        let elements = self.free_elements();
+        let len = elements.required_len() + elements.len();
+        let max = max.min(len);
+        let layer = if min > max {
+            KSubsetIter::new(elements.clone(), len + 1)
+        } else {
+            KSubsetIter::new(elements.clone(), min)
+        };
+        BoundedSubsetIter {
+            elements,
+            size: min,
+            max,
+            layer,
+        }
+    }
+}
+
+impl<'a, T> Iterator for BoundedSubsetIter<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(subset) = self.layer.next() {
+                return Some(subset);
+            }
+            if self.size >= self.max {
+                return None;
+            }
+            self.size += 1;
+            self.layer = KSubsetIter::new(self.elements.clone(), self.size);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+
+    #[test]
+    fn bounded_nondecreasing() {
+        let data: Vec<usize> = (0..7).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        let sizes: Vec<usize> = generator.bounded(2, 4).map(|s| s.len()).collect();
+        assert_eq!(sizes.len(), 21 + 35 + 35);
+        assert!(sizes.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(sizes.first(), Some(&2));
+        assert_eq!(sizes.last(), Some(&4));
+    }
+
+    #[test]
+    fn bounded_full_range() {
+        let data: Vec<usize> = (0..5).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        assert_eq!(generator.bounded(0, 5).count(), 32);
+        assert_eq!(generator.bounded(1, 100).count(), 31);
+    }
+
+    #[test]
+    fn bounded_empty_range() {
+        let data = vec![1, 2, 3];
+        let generator = SubsetGenerator::new(&data, true);
+        assert_eq!(generator.bounded(2, 1).count(), 0);
+        assert_eq!(generator.bounded(4, 10).count(), 0);
+        assert_eq!(generator.bounded(3, 3).count(), 1);
+    }
+}
@@ -0,0 +1,430 @@
+use crate::{BoundedSubsetIter, GraySubsetIter, SubsetError, SubsetGenerator, SubsetIter};
+use alloc::vec::Vec;
+
+/// The order in which [`SubsetGenerator::subsets`] reports the subsets.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Order {
+    /// Increasing order of the bitmask, as reported by `iter`.
+    #[default]
+    Binary,
+    /// Reflected binary Gray-code order, as reported by `gray_iter`.
+    Gray,
+    /// Nondecreasing order of cardinality, as reported by `bounded`.
+    Cardinality,
+}
+
+/// Where [`SubsetGenerator::subsets`] reports the empty set.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EmptysetPosition {
+    /// Before all other subsets, as `iter` reports it.
+    First,
+    /// After all other subsets, so the nonempty subsets are tried first.
+    Last,
+    /// Not at all.
+    #[default]
+    None,
+}
+
+/// Builder for a [`SubsetGenerator`] with more configuration than
+/// [`SubsetGenerator::new`] takes, created by [`SubsetGenerator::builder`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::{Order, SubsetGenerator};
+/// let data = vec![1, 2, 3, 4];
+///
+/// let sg = SubsetGenerator::builder(&data)
+///     .size_range(2, 3)
+///     .order(Order::Cardinality)
+///     .build()
+///     .unwrap();
+/// assert_eq!(sg.subsets().count(), 10);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SubsetGeneratorBuilder<'a, T> {
+    data: &'a [T],
+    emptyset: EmptysetPosition,
+    required: Vec<usize>,
+    forbidden: Vec<usize>,
+    size: Option<(usize, usize)>,
+    order: Order,
+    report_interval: Option<u64>,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns a builder for a generator over `data`, which does not include
+    /// the empty set unless configured otherwise. The simple cases are still
+    /// best served by [`new`](Self::new).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::{SubsetError, SubsetGenerator};
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::builder(&data)
+    ///     .emptyset(true)
+    ///     .require(&[0])
+    ///     .forbid(&[2])
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(sg.subsets().collect::<Vec<_>>(), vec![vec![&1], vec![&1, &2]]);
+    ///
+    /// let result = SubsetGenerator::builder(&data).require(&[1]).forbid(&[1]).build();
+    /// assert_eq!(result.unwrap_err(), SubsetError::Conflict { index: 1 });
+    /// ```
+    pub fn builder<D>(data: &'a D) -> SubsetGeneratorBuilder<'a, T>
+    where
+        D: AsRef<[T]> + ?Sized,
+    {
+        SubsetGeneratorBuilder {
+            data: data.as_ref(),
+            emptyset: EmptysetPosition::None,
+            required: Vec::new(),
+            forbidden: Vec::new(),
+            size: None,
+            order: Order::Binary,
+            report_interval: None,
+        }
+    }
+
+    /// Returns an iterator over the subsets as configured with
+    /// [`builder`](Self::builder): in the configured [`Order`], and restricted
+    /// to the configured size range, with the empty set at the configured
+    /// [`EmptysetPosition`]. For a generator constructed with `new`, this
+    /// reports the same subsets as `iter`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::{Order, SubsetGenerator};
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::builder(&data).order(Order::Gray).build().unwrap();
+    /// let mut iter = sg.subsets();
+    ///
+    /// assert_eq!(iter.next(), Some(vec![&1]));
+    /// assert_eq!(iter.next(), Some(vec![&1, &2]));
+    /// assert_eq!(iter.next(), Some(vec![&2]));
+    /// ```
+    pub fn subsets(&self) -> Subsets<'a, T> {
+        let (min, max) = self.size.unwrap_or((0, usize::MAX));
+        let inner = match self.order {
+            Order::Binary => Ordered::Binary(self.iter()),
+            Order::Gray => Ordered::Gray(self.gray_iter()),
+            Order::Cardinality => {
+                Ordered::Cardinality(self.bounded(min.max(!self.with_emptyset as usize), max))
+            }
+        };
+        Subsets {
+            inner,
+            min,
+            max,
+            buf: Vec::new(),
+            empty_last: self.emptyset_last && min == 0 && !self.includes_base(),
+        }
+    }
+}
+
+impl<'a, T> SubsetGeneratorBuilder<'a, T> {
+    /// Sets whether the empty set is reported, which it is not by default. If
+    /// it is, it is reported first, see
+    /// [`emptyset_position`](Self::emptyset_position).
+    pub fn emptyset(mut self, with_emptyset: bool) -> Self {
+        self.emptyset = if with_emptyset {
+            EmptysetPosition::First
+        } else {
+            EmptysetPosition::None
+        };
+        self
+    }
+
+    /// Sets whether, and where, [`SubsetGenerator::subsets`] reports the empty
+    /// set. With [`EmptysetPosition::Last`], it comes after all the other
+    /// subsets in the configured order, which is after the full set in the
+    /// binary order. The other iterators of the generator then do not report
+    /// the empty set at all. With required elements, there is no empty set,
+    /// and the position has no effect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::{EmptysetPosition, SubsetGenerator};
+    ///
+    /// let data = vec![1, 2];
+    /// let sg = SubsetGenerator::builder(&data)
+    ///     .emptyset_position(EmptysetPosition::Last)
+    ///     .build()
+    ///     .unwrap();
+    /// let subsets: Vec<_> = sg.subsets().collect();
+    /// assert_eq!(subsets, vec![vec![&1], vec![&2], vec![&1, &2], vec![]]);
+    /// ```
+    pub fn emptyset_position(mut self, position: EmptysetPosition) -> Self {
+        self.emptyset = position;
+        self
+    }
+
+    /// Requires the elements at the given indices to be in every subset, as
+    /// [`SubsetGenerator::with_required`] does.
+    pub fn require(mut self, indices: &[usize]) -> Self {
+        self.required.extend_from_slice(indices);
+        self
+    }
+
+    /// Forbids the elements at the given indices from every subset, as
+    /// [`SubsetGenerator::with_forbidden`] does.
+    pub fn forbid(mut self, indices: &[usize]) -> Self {
+        self.forbidden.extend_from_slice(indices);
+        self
+    }
+
+    /// Restricts [`SubsetGenerator::subsets`] to the subsets with at least
+    /// `min` and at most `max` elements.
+    pub fn size_range(mut self, min: usize, max: usize) -> Self {
+        self.size = Some((min, max));
+        self
+    }
+
+    /// Sets the order of [`SubsetGenerator::subsets`], which is
+    /// [`Order::Binary`] by default.
+    pub fn order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Sets the number of subsets between two reports of
+    /// [`SubsetGenerator::search`], which is `2^20` by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    pub fn report_interval(mut self, interval: u64) -> Self {
+        assert!(interval > 0, "the report interval must be positive");
+        self.report_interval = Some(interval);
+        self
+    }
+
+    /// Validates the configuration and constructs the generator.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a required or forbidden index is out of range, if
+    /// an index is both required and forbidden, if the size range is empty, or
+    /// if indices are required or forbidden with an order other than
+    /// [`Order::Binary`].
+    pub fn build(self) -> Result<SubsetGenerator<'a, T>, SubsetError> {
+        let len = self.data.len();
+        for &index in self.required.iter().chain(&self.forbidden) {
+            if index >= len {
+                return Err(SubsetError::IndexOutOfRange { index, len });
+            }
+        }
+        if let Some(&index) = self.required.iter().find(|i| self.forbidden.contains(i)) {
+            return Err(SubsetError::Conflict { index });
+        }
+        if let Some((min, max)) = self.size {
+            if min > max {
+                return Err(SubsetError::EmptySizeRange { min, max });
+            }
+        }
+        let constrained = !self.required.is_empty() || !self.forbidden.is_empty();
+        if constrained && self.order != Order::Binary {
+            return Err(SubsetError::UnsupportedOrder(self.order));
+        }
+
+        let with_emptyset = self.emptyset == EmptysetPosition::First;
+        let mut generator = SubsetGenerator::new(self.data, with_emptyset);
+        if constrained {
+            generator = generator
+                .with_required(&self.required)
+                .with_forbidden(&self.forbidden);
+        }
+        generator.size = self.size;
+        generator.order = self.order;
+        generator.emptyset_last = self.emptyset == EmptysetPosition::Last;
+        if let Some(interval) = self.report_interval {
+            generator.report_interval = interval;
+        }
+        Ok(generator)
+    }
+}
+
+/// Iterator over the subsets in the configured order and size range, created
+/// by [`SubsetGenerator::subsets`].
+pub struct Subsets<'a, T> {
+    inner: Ordered<'a, T>,
+    min: usize,
+    max: usize,
+    buf: Vec<&'a T>,
+    empty_last: bool,
+}
+
+enum Ordered<'a, T> {
+    Binary(SubsetIter<'a, T>),
+    Gray(GraySubsetIter<'a, T>),
+    Cardinality(BoundedSubsetIter<'a, T>),
+}
+
+impl<'a, T> Iterator for Subsets<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let subset = self.next_inner();
+        if subset.is_none() && self.empty_last {
+            self.empty_last = false;
+            return Some(Vec::new());
+        }
+        subset
+    }
+}
+
+impl<'a, T> Subsets<'a, T> {
+    /// Returns the next subset in the configured order and size range, not
+    /// counting an empty set that is reported last.
+    fn next_inner(&mut self) -> Option<Vec<&'a T>> {
+        let (min, max) = (self.min, self.max);
+        match &mut self.inner {
+            // Subsets outside the size range are rejected from the buffer, so
+            // only the reported ones are allocated.
+            Ordered::Binary(iter) => {
+                while iter.next_into(&mut self.buf) {
+                    if (min..=max).contains(&self.buf.len()) {
+                        return Some(self.buf.clone());
+                    }
+                }
+                None
+            }
+            Ordered::Gray(iter) => iter.find(|s| (min..=max).contains(&s.len())),
+            Ordered::Cardinality(iter) => iter.next(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{EmptysetPosition, Order, SubsetError, SubsetGenerator};
+
+    #[test]
+    fn builder_defaults_match_new() {
+        let data: Vec<usize> = (0..6).collect();
+        let built = SubsetGenerator::builder(&data).build().unwrap();
+        let expected: Vec<_> = SubsetGenerator::new(&data, false).iter().collect();
+        assert_eq!(built.subsets().collect::<Vec<_>>(), expected);
+        assert_eq!(built.iter().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn builder_orders_and_sizes() {
+        let data: Vec<usize> = (0..6).collect();
+        for &with_emptyset in &[false, true] {
+            for &order in &[Order::Binary, Order::Gray, Order::Cardinality] {
+                for &(min, max) in &[(0, 6), (0, 2), (2, 4), (5, 9)] {
+                    let generator = SubsetGenerator::builder(&data)
+                        .emptyset(with_emptyset)
+                        .size_range(min, max)
+                        .order(order)
+                        .build()
+                        .unwrap();
+                    let mut actual: Vec<_> = generator.subsets().collect();
+                    let mut expected: Vec<_> = SubsetGenerator::new(&data, with_emptyset)
+                        .iter()
+                        .filter(|s| (min..=max).contains(&s.len()))
+                        .collect();
+                    actual.sort();
+                    expected.sort();
+                    assert_eq!(actual, expected, "{:?} {}..={}", order, min, max);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn emptyset_positions() {
+        let data = vec![1, 2, 3];
+        let build = |position, order| {
+            SubsetGenerator::builder(&data)
+                .emptyset_position(position)
+                .order(order)
+                .build()
+                .unwrap()
+        };
+        let nonempty: Vec<_> = SubsetGenerator::new(&data, false).iter().collect();
+
+        let first: Vec<_> = build(EmptysetPosition::First, Order::Binary)
+            .subsets()
+            .collect();
+        assert_eq!(first[0], Vec::<&i32>::new());
+        assert_eq!(first[1..], nonempty[..]);
+
+        let last: Vec<_> = build(EmptysetPosition::Last, Order::Binary)
+            .subsets()
+            .collect();
+        assert_eq!(last[..7], nonempty[..]);
+        assert_eq!(last[7], Vec::<&i32>::new());
+        assert_eq!(last.len(), 8);
+
+        let none: Vec<_> = build(EmptysetPosition::None, Order::Binary)
+            .subsets()
+            .collect();
+        assert_eq!(none, nonempty);
+
+        for &order in &[Order::Gray, Order::Cardinality] {
+            let last: Vec<_> = build(EmptysetPosition::Last, order).subsets().collect();
+            assert_eq!(last.len(), 8);
+            assert!(last[..7].iter().all(|s| !s.is_empty()));
+            assert!(last[7].is_empty());
+        }
+
+        // Without 0 in the size range, or with required elements, there is no
+        // empty set to report.
+        let sized = SubsetGenerator::builder(&data)
+            .emptyset_position(EmptysetPosition::Last)
+            .size_range(1, 3)
+            .build()
+            .unwrap();
+        assert_eq!(sized.subsets().count(), 7);
+        let required = SubsetGenerator::builder(&data)
+            .emptyset_position(EmptysetPosition::Last)
+            .require(&[1])
+            .build()
+            .unwrap();
+        assert_eq!(required.subsets().count(), 4);
+        assert_eq!(required.subsets().next(), Some(vec![&2]));
+    }
+
+    #[test]
+    fn builder_validation() {
+        let data = vec![1, 2, 3];
+        let build = |required: &[usize], forbidden: &[usize]| {
+            SubsetGenerator::builder(&data)
+                .require(required)
+                .forbid(forbidden)
+                .build()
+                .map(|_| ())
+        };
+        assert_eq!(build(&[0], &[2]), Ok(()));
+        assert_eq!(
+            build(&[3], &[]),
+            Err(SubsetError::IndexOutOfRange { index: 3, len: 3 })
+        );
+        assert_eq!(
+            build(&[1], &[0, 1]),
+            Err(SubsetError::Conflict { index: 1 })
+        );
+
+        let result = SubsetGenerator::builder(&data).size_range(2, 1).build();
+        assert_eq!(
+            result.map(|_| ()),
+            Err(SubsetError::EmptySizeRange { min: 2, max: 1 })
+        );
+        let result = SubsetGenerator::builder(&data)
+            .forbid(&[0])
+            .order(Order::Gray)
+            .build();
+        assert_eq!(
+            result.map(|_| ()),
+            Err(SubsetError::UnsupportedOrder(Order::Gray))
+        );
+    }
+}
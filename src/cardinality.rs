@@ -0,0 +1,161 @@
+use crate::constraints::FreeElements;
+use crate::{KSubsetIter, SubsetGenerator};
+use alloc::vec::Vec;
+
+/// Iterator over the subsets grouped by their number of elements, created by
+/// [`SubsetGenerator::by_cardinality`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// let sizes: Vec<usize> = sg.by_cardinality().map(|(size, _)| size).collect();
+/// assert_eq!(sizes, vec![1, 2, 3]);
+/// ```
+pub struct CardinalityGroupedIter<'a, T> {
+    elements: FreeElements<'a, T>,
+    size: usize,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over groups of subsets of the same size. Each item
+    /// is a size `k` followed by all the subsets with exactly `k` elements, in
+    /// the order of [`combinations`](Self::combinations), and the groups are
+    /// reported in increasing order of `k`. The first group is the one of size
+    /// 0 (holding just the empty set) if the generator was configured to
+    /// include the empty set, and the one of size 1 otherwise. Dropping the
+    /// iterator after the group of some size skips all the larger subsets.
+    ///
+    /// The constraints of the generator are respected as by `combinations`:
+    /// with `r` required elements, the first group is the one of size `r`,
+    /// holding just the required elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, true);
+    /// let mut iter = sg.by_cardinality();
+    ///
+    /// assert_eq!(iter.next(), Some((0, vec![vec![]])));
+    /// assert_eq!(iter.next(), Some((1, vec![vec![&1], vec![&2], vec![&3]])));
+    /// assert_eq!(iter.next(), Some((2, vec![vec![&1, &2], vec![&1, &3], vec![&2, &3]])));
+    /// assert_eq!(iter.next(), Some((3, vec![vec![&1, &2, &3]])));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn by_cardinality(&self) -> CardinalityGroupedIter<'a, T> {
+        let elements = self.free_elements();
+        CardinalityGroupedIter {
+            size: elements.required_len() + !self.includes_base() as usize,
+            elements,
+        }
+    }
+
+    /// Returns one lazy iterator per cardinality layer, for the sizes from 0
+    /// (if the generator includes the empty set) or 1 up to `n`, in increasing
+    /// order. Unlike [`by_cardinality`](Self::by_cardinality), no layer is
+    /// collected, so each layer only needs *O(n)* memory, and the layers can
+    /// be handed to different threads and processed concurrently. The
+    /// constraints of the generator are respected, like by `by_cardinality`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3, 4];
+    /// let sg = SubsetGenerator::new(&data, false);
+    ///
+    /// let counts: Vec<usize> = sg.layers().map(|layer| layer.count()).collect();
+    /// assert_eq!(counts, vec![4, 6, 4, 1]);
+    /// ```
+    pub fn layers(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = KSubsetIter<'a, T>> + ExactSizeIterator {
+        let elements = self.free_elements();
+        let first = elements.required_len() + !self.includes_base() as usize;
+        let last = elements.required_len() + elements.len();
+        (first..last + 1).map(move |size| KSubsetIter::new(elements.clone(), size))
+    }
+}
+
+impl<'a, T> Iterator for CardinalityGroupedIter<'a, T> {
+    type Item = (usize, Vec<Vec<&'a T>>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.size > self.elements.required_len() + self.elements.len() {
+            return None;
+        }
+        let size = self.size;
+        self.size += 1;
+        Some((
+            size,
+            KSubsetIter::new(self.elements.clone(), size).collect(),
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let last = self.elements.required_len() + self.elements.len();
+        let groups = (last + 1).saturating_sub(self.size);
+        (groups, Some(groups))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for CardinalityGroupedIter<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+
+    #[test]
+    fn groups_cover_all_subsets() {
+        let data: Vec<usize> = (0..6).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let mut total = 0;
+            let mut previous = None;
+            for (size, subsets) in generator.by_cardinality() {
+                assert!(previous.map_or(true, |p| p + 1 == size));
+                assert!(subsets.iter().all(|s| s.len() == size));
+                assert_eq!(subsets, generator.combinations(size).collect::<Vec<_>>());
+                total += subsets.len();
+                previous = Some(size);
+            }
+            assert_eq!(total, generator.iter().count());
+        }
+    }
+
+    #[test]
+    fn groups_of_empty_dataset() {
+        let data: Vec<u32> = vec![];
+        assert_eq!(SubsetGenerator::new(&data, false).by_cardinality().len(), 0);
+        let generator = SubsetGenerator::new(&data, true);
+        assert_eq!(generator.by_cardinality().len(), 1);
+        assert_eq!(
+            generator.by_cardinality().next(),
+            Some((0, vec![Vec::<&u32>::new()]))
+        );
+    }
+
+    #[test]
+    fn layers_cover_powerset() {
+        let data: Vec<usize> = (0..9).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let layers = generator.layers();
+            assert_eq!(layers.len(), 9 + with_emptyset as usize);
+            let mut total = 0;
+            for (layer, group) in layers.zip(generator.by_cardinality()) {
+                let layer: Vec<_> = layer.collect();
+                total += layer.len();
+                assert_eq!(layer, group.1);
+            }
+            assert_eq!(total, (1 << 9) - 1 + with_emptyset as usize);
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{IndexSubsetIter, SubsetGenerator, SubsetIter};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// Iterator over all the subsets as owned vectors, created by
+/// [`SubsetGenerator::iter_cloned`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2];
+/// let sg = SubsetGenerator::new(&data, true);
+///
+/// let subsets: Vec<Vec<i32>> = sg.iter_cloned().collect();
+/// assert_eq!(subsets, vec![vec![], vec![1], vec![2], vec![1, 2]]);
+/// ```
+pub struct ClonedSubsetIter<'a, T> {
+    iter: SubsetIter<'a, T>,
+}
+
+/// Iterator over all the subsets as owned vectors that does not borrow the
+/// dataset, created by [`SubsetGenerator::into_owned_iter`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::{OwnedClonedIter, SubsetGenerator};
+///
+/// let iter: OwnedClonedIter<i32> = {
+///     let data = vec![1, 2];
+///     SubsetGenerator::new(&data, false).into_owned_iter()
+/// };
+/// assert_eq!(iter.collect::<Vec<_>>(), vec![vec![1], vec![2], vec![1, 2]]);
+/// ```
+pub struct OwnedClonedIter<T> {
+    data: Vec<T>,
+    indices: IndexSubsetIter<'static, ()>,
+}
+
+impl<'a, T: Clone> SubsetGenerator<'a, T> {
+    /// Returns an iterator over all the subsets, where each subset holds clones
+    /// of its elements instead of references. The subsets can then outlive the
+    /// dataset, or be sent to another thread. The subsets are reported in the
+    /// same order as by `iter`, including the empty set if (and only if) the
+    /// generator was configured to include it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let subsets: Vec<Vec<String>> = {
+    ///     let data = vec![String::from("a"), String::from("b")];
+    ///     SubsetGenerator::new(&data, false).iter_cloned().collect()
+    /// };
+    /// assert_eq!(subsets[2], vec!["a", "b"]);
+    /// ```
+    pub fn iter_cloned(&self) -> ClonedSubsetIter<'a, T> {
+        ClonedSubsetIter { iter: self.iter() }
+    }
+}
+
+impl<'a, T: Clone> SubsetGenerator<'a, T> {
+    /// Consumes the generator and returns an iterator that owns a copy of the
+    /// dataset, and reports every subset as a vector of clones of its
+    /// elements, in the same order as `iter`. Unlike
+    /// [`iter_cloned`](Self::iter_cloned), the iterator itself does not borrow
+    /// the dataset, so with `T: 'static` it can be moved into another thread,
+    /// for instance to feed the subsets into a `std::sync::mpsc` channel. The
+    /// dataset is cloned once up front; the iterator then tracks the subsets by
+    /// the indices of their elements. The constraints of the generator are
+    /// respected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::mpsc;
+    /// use std::thread;
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![String::from("a"), String::from("b")];
+    /// let subsets = SubsetGenerator::new(&data, false).into_owned_iter();
+    ///
+    /// let (sender, receiver) = mpsc::channel();
+    /// thread::spawn(move || subsets.for_each(|s| sender.send(s).unwrap()));
+    /// assert_eq!(receiver.iter().count(), 3);
+    /// ```
+    pub fn into_owned_iter(self) -> OwnedClonedIter<T> {
+        // The indices are enumerated over a dataset of units of the same
+        // length, which is free to create: a vector of a zero-sized type
+        // never allocates, so leaking it leaks nothing.
+        let units: &'static [()] = Vec::leak(vec![(); self.data.len()]);
+        let positions = SubsetGenerator {
+            data: units,
+            with_emptyset: self.with_emptyset,
+            constraints: self.constraints,
+            size: self.size,
+            order: self.order,
+            emptyset_last: self.emptyset_last,
+            report_interval: self.report_interval,
+        };
+        OwnedClonedIter {
+            data: self.data.to_vec(),
+            indices: positions.index_iter(),
+        }
+    }
+}
+
+impl<'a, T: Clone> Iterator for ClonedSubsetIter<'a, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let subset = self.iter.next()?;
+        Some(subset.into_iter().cloned().collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T: Clone> DoubleEndedIterator for ClonedSubsetIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let subset = self.iter.next_back()?;
+        Some(subset.into_iter().cloned().collect())
+    }
+}
+
+impl<'a, T: Clone> ExactSizeIterator for ClonedSubsetIter<'a, T> {}
+
+impl<'a, T: Clone> FusedIterator for ClonedSubsetIter<'a, T> {}
+
+impl<T: Clone> Iterator for OwnedClonedIter<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let indices = self.indices.next()?;
+        Some(indices.into_iter().map(|i| self.data[i].clone()).collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl<T: Clone> ExactSizeIterator for OwnedClonedIter<T> {}
+
+impl<T: Clone> FusedIterator for OwnedClonedIter<T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+
+    #[test]
+    fn cloned_matches_iter() {
+        let data: Vec<String> = (0..5).map(|i| i.to_string()).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let cloned: Vec<Vec<String>> = generator.iter_cloned().collect();
+            let expected: Vec<Vec<String>> = generator
+                .iter()
+                .map(|s| s.into_iter().cloned().collect())
+                .collect();
+            assert_eq!(cloned, expected);
+            assert_eq!(generator.iter_cloned().len(), expected.len());
+        }
+    }
+
+    #[test]
+    fn cloned_outlives_data() {
+        let handle = {
+            let data = vec![1, 2, 3];
+            let subsets: Vec<Vec<i32>> = SubsetGenerator::new(&data, false).iter_cloned().collect();
+            std::thread::spawn(move || subsets.len())
+        };
+        assert_eq!(handle.join().unwrap(), 7);
+    }
+
+    #[test]
+    fn owned_matches_cloned() {
+        let data: Vec<String> = (0..6).map(|i| i.to_string()).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset).with_required(&[4]);
+            let expected: Vec<Vec<String>> = generator.iter_cloned().collect();
+            let owned = generator.into_owned_iter();
+            assert_eq!(owned.len(), expected.len());
+            assert_eq!(owned.collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn owned_across_channel() {
+        use std::sync::mpsc;
+
+        let (sender, receiver) = mpsc::channel();
+        let producer = {
+            let data = vec![1u32, 2, 3, 4];
+            let subsets = SubsetGenerator::new(&data, true).into_owned_iter();
+            std::thread::spawn(move || {
+                for subset in subsets.take(5) {
+                    sender.send(subset).unwrap();
+                }
+            })
+        };
+        let received: Vec<Vec<u32>> = receiver.iter().collect();
+        producer.join().unwrap();
+        assert_eq!(
+            received,
+            vec![vec![], vec![1], vec![2], vec![1, 2], vec![3]]
+        );
+    }
+}
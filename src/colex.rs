@@ -0,0 +1,166 @@
+use crate::{SubsetGenerator, SubsetIter};
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// Iterator over all the subsets in colexicographic order, created by
+/// [`SubsetGenerator::colex_iter`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2];
+/// let sg = SubsetGenerator::new(&data, true);
+///
+/// let subsets: Vec<Vec<&i32>> = sg.colex_iter().collect();
+/// assert_eq!(subsets, vec![vec![], vec![&1], vec![&2], vec![&1, &2]]);
+/// ```
+pub struct ColexSubsetIter<'a, T> {
+    iter: SubsetIter<'a, T>,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over all the subsets in colexicographic order of
+    /// their index sets: a subset `A` comes before `B` if the largest index in
+    /// which they differ belongs to `B`. Equivalently, the index sets are
+    /// compared as sequences sorted in decreasing order. This is the order
+    /// used by combinadic ranking, and restricted to the subsets of a fixed
+    /// size it is the order of [`combinations`](Self::combinations).
+    ///
+    /// The largest differing index is the highest differing bit of the masks,
+    /// so colex order coincides with the binary counting order of `iter`, and
+    /// this iterator supports the same operations from both ends. It exists to
+    /// state the order explicitly, which `iter` does not promise to keep.
+    ///
+    /// Colex order refers to the indices of the dataset, so an order set with
+    /// [`with_order`](Self::with_order) is ignored: the bits of the cursor are
+    /// assigned to the elements in the order of the dataset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.colex_iter();
+    ///
+    /// assert_eq!(iter.next(), Some(vec![&1]));
+    /// assert_eq!(iter.next(), Some(vec![&2]));
+    /// assert_eq!(iter.next(), Some(vec![&1, &2]));
+    /// assert_eq!(iter.next(), Some(vec![&3]));
+    /// ```
+    pub fn colex_iter(&self) -> ColexSubsetIter<'a, T> {
+        let mut iter = self.iter();
+        if let Some(constraints) = &mut iter.constraints {
+            constraints.clear_order(self.data.len());
+        }
+        ColexSubsetIter { iter }
+    }
+}
+
+impl<'a, T> Iterator for ColexSubsetIter<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for ColexSubsetIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for ColexSubsetIter<'a, T> {}
+
+impl<'a, T> FusedIterator for ColexSubsetIter<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+    use std::cmp::Ordering;
+
+    /// Compares two index sets in colexicographic order.
+    fn colex(a: &[usize], b: &[usize]) -> Ordering {
+        a.iter().rev().cmp(b.iter().rev())
+    }
+
+    #[test]
+    fn colex_four_elements() {
+        let data: Vec<usize> = (0..4).collect();
+        let generator = SubsetGenerator::new(&data, true);
+        let subsets: Vec<Vec<usize>> = generator
+            .colex_iter()
+            .map(|s| s.into_iter().copied().collect())
+            .collect();
+        let expected: Vec<Vec<usize>> = vec![
+            vec![],
+            vec![0],
+            vec![1],
+            vec![0, 1],
+            vec![2],
+            vec![0, 2],
+            vec![1, 2],
+            vec![0, 1, 2],
+            vec![3],
+            vec![0, 3],
+            vec![1, 3],
+            vec![0, 1, 3],
+            vec![2, 3],
+            vec![0, 2, 3],
+            vec![1, 2, 3],
+            vec![0, 1, 2, 3],
+        ];
+        assert_eq!(subsets, expected);
+    }
+
+    #[test]
+    fn colex_is_sorted() {
+        let data: Vec<usize> = (0..9).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        let subsets: Vec<Vec<usize>> = generator
+            .colex_iter()
+            .map(|s| s.into_iter().copied().collect())
+            .collect();
+        assert!(subsets
+            .windows(2)
+            .all(|w| colex(&w[0], &w[1]) == Ordering::Less));
+
+        let pairs: Vec<Vec<usize>> = subsets.into_iter().filter(|s| s.len() == 2).collect();
+        let combinations: Vec<Vec<usize>> = generator
+            .combinations(2)
+            .map(|s| s.into_iter().copied().collect())
+            .collect();
+        assert_eq!(pairs, combinations);
+    }
+
+    #[test]
+    fn colex_ignores_order() {
+        let data: Vec<usize> = (0..5).collect();
+        let generator = SubsetGenerator::new(&data, true)
+            .with_required(&[3])
+            .with_order(&[2, 4, 0, 3, 1])
+            .unwrap();
+        let subsets: Vec<Vec<usize>> = generator
+            .colex_iter()
+            .map(|s| s.into_iter().copied().collect())
+            .collect();
+        assert_eq!(subsets.len(), 16);
+        assert!(subsets.iter().all(|s| s.contains(&3)));
+        assert!(subsets
+            .windows(2)
+            .all(|w| colex(&w[0], &w[1]) == Ordering::Less));
+        assert_eq!(subsets[0], vec![3]);
+        assert_eq!(subsets[1], vec![0, 3]);
+
+        let mut iter = generator.colex_iter();
+        assert_eq!(iter.next_back(), Some(vec![&0, &1, &2, &3, &4]));
+        assert_eq!(iter.next_back(), Some(vec![&1, &2, &3, &4]));
+    }
+}
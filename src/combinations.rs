@@ -0,0 +1,164 @@
+use crate::constraints::FreeElements;
+use crate::SubsetGenerator;
+use alloc::vec::Vec;
+use bit_vec::BitVec;
+
+/// Iterator over the subsets of exactly `k` elements of a dataset, created by
+/// [`SubsetGenerator::combinations`]. Only the `C(n, k)` subsets of the
+/// requested size are generated, rather than filtering the whole powerset.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2, 3, 4];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// assert_eq!(sg.combinations(2).count(), 6);
+/// ```
+pub struct KSubsetIter<'a, T> {
+    elements: FreeElements<'a, T>,
+    set: BitVec,
+    first: bool,
+    exhausted: bool,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over all the subsets with exactly `k` elements. The
+    /// subsets are reported in increasing order of their bitmask, so the first
+    /// subset consists of the first `k` elements of the dataset. For `k == 0`
+    /// the iterator yields a single empty subset, regardless of whether the
+    /// generator was configured to include the empty set, and for `k > n` it
+    /// yields nothing.
+    ///
+    /// The constraints of the generator are respected: `k` still counts all
+    /// the elements of a subset, of which the required ones are fixed, and
+    /// the bits of the mask follow the order of
+    /// [`with_order`](Self::with_order), as for `iter`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.combinations(2);
+    ///
+    /// assert_eq!(iter.next(), Some(vec![&1, &2]));
+    /// assert_eq!(iter.next(), Some(vec![&1, &3]));
+    /// assert_eq!(iter.next(), Some(vec![&2, &3]));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn combinations(&self, k: usize) -> KSubsetIter<'a, T> {
+        KSubsetIter::new(self.free_elements(), k)
+    }
+}
+
+impl<'a, T> KSubsetIter<'a, T> {
+    /// Constructs an iterator over the subsets of `elements` with `k`
+    /// elements, counting the required ones.
+    pub(crate) fn new(elements: FreeElements<'a, T>, k: usize) -> KSubsetIter<'a, T> {
+        let len = elements.len();
+        let free = k
+            .checked_sub(elements.required_len())
+            .filter(|&free| free <= len);
+        KSubsetIter {
+            set: BitVec::from_fn(len, |i| free.is_some_and(|free| i < free)),
+            elements,
+            first: true,
+            exhausted: free.is_none(),
+        }
+    }
+
+    /// Computes the next mask with the same number of set bits. The lowest run
+    /// of set bits is shifted up: its highest bit moves one position up, and
+    /// the rest of the run moves down to the lowest positions. Returns false if
+    /// the run already ends at the final bit, as then all subsets of this size
+    /// have been exhausted.
+    fn next_set(&mut self) -> bool {
+        let len = self.set.len();
+        let mut low = 0;
+        while low < len && !self.set[low] {
+            low += 1;
+        }
+        let mut high = low;
+        while high < len && self.set[high] {
+            high += 1;
+        }
+        if high == len {
+            return false;
+        }
+
+        self.set.set(high, true);
+        for i in 0..high {
+            self.set.set(i, i < high - low - 1);
+        }
+        true
+    }
+}
+
+impl<'a, T> Iterator for KSubsetIter<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+
+        if self.first {
+            self.first = false;
+        } else if !self.next_set() {
+            self.exhausted = true;
+            return None;
+        }
+        Some(self.elements.subset(&self.set))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+
+    fn binomial(n: usize, k: usize) -> usize {
+        (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+    }
+
+    #[test]
+    fn combinations_count() {
+        let data: Vec<usize> = (0..8).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        for k in 0..=8 {
+            let mut iters = 0;
+            for subset in generator.combinations(k) {
+                assert_eq!(subset.len(), k);
+                iters += 1;
+            }
+            assert_eq!(iters, binomial(8, k));
+        }
+    }
+
+    #[test]
+    fn combinations_match_filtered_powerset() {
+        let data: Vec<usize> = (0..6).collect();
+        let generator = SubsetGenerator::new(&data, true);
+        for k in 0..=6 {
+            let filtered: Vec<_> = generator.iter().filter(|s| s.len() == k).collect();
+            let combinations: Vec<_> = generator.combinations(k).collect();
+            assert_eq!(combinations, filtered);
+        }
+    }
+
+    #[test]
+    fn combinations_edge_cases() {
+        let data = vec![1, 2, 3];
+        let generator = SubsetGenerator::new(&data, false);
+        assert_eq!(generator.combinations(0).collect::<Vec<_>>(), vec![Vec::<&i32>::new()]);
+        assert_eq!(generator.combinations(4).next(), None);
+
+        let empty: Vec<i32> = vec![];
+        let generator = SubsetGenerator::new(&empty, false);
+        assert_eq!(generator.combinations(0).count(), 1);
+        assert_eq!(generator.combinations(1).count(), 0);
+    }
+}
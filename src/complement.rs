@@ -0,0 +1,107 @@
+use crate::{SubsetGenerator, SubsetIter};
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// Iterator over all the subsets paired with their complements, created by
+/// [`SubsetGenerator::with_complement`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// let mut iter = sg.with_complement();
+/// assert_eq!(iter.next(), Some((vec![&1], vec![&2, &3])));
+/// ```
+pub struct ComplementIter<'a, T> {
+    iter: SubsetIter<'a, T>,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over all the subsets, where each subset `A` is
+    /// paired with its complement `U \ A`, the elements of the dataset that are
+    /// not in `A`. Both halves list their elements in the order of the dataset.
+    /// The subsets are reported in the same order as by `iter`, so every pair
+    /// appears exactly once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![4, 7, 5];
+    /// let sg = SubsetGenerator::new(&data, true);
+    /// let balanced = sg.with_complement().find(|(a, b)| {
+    ///     a.iter().copied().sum::<i32>() == b.iter().copied().sum::<i32>()
+    /// });
+    /// assert_eq!(balanced, None);
+    ///
+    /// let (a, b) = sg.with_complement().last().unwrap();
+    /// assert_eq!((a.len(), b.len()), (3, 0));
+    /// ```
+    pub fn with_complement(&self) -> ComplementIter<'a, T> {
+        ComplementIter { iter: self.iter() }
+    }
+}
+
+impl<'a, T> Iterator for ComplementIter<'a, T> {
+    type Item = (Vec<&'a T>, Vec<&'a T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The pending base subset is reported before the cursor moves.
+        let base = self.iter.with_emptyset;
+        let subset = self.iter.next()?;
+        let mut complement = Vec::with_capacity(self.iter.data.len() - subset.len());
+        let mask = if base { None } else { Some(&self.iter.set) };
+        self.iter.complement_into(mask, &mut complement);
+        Some((subset, complement))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for ComplementIter<'a, T> {}
+
+impl<'a, T> FusedIterator for ComplementIter<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+    use std::collections::HashSet;
+
+    #[test]
+    fn complements_partition() {
+        let data: Vec<usize> = (0..7).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let mut seen = HashSet::new();
+            for (subset, complement) in generator.with_complement() {
+                let a: HashSet<usize> = subset.iter().map(|&&x| x).collect();
+                let b: HashSet<usize> = complement.iter().map(|&&x| x).collect();
+                assert!(a.is_disjoint(&b));
+                assert_eq!(a.len() + b.len(), data.len());
+                assert!(seen.insert((subset, complement)));
+            }
+            assert_eq!(seen.len(), generator.iter().len());
+        }
+    }
+
+    #[test]
+    fn complements_with_constraints() {
+        let data: Vec<usize> = (0..5).collect();
+        let generator = SubsetGenerator::new(&data, false)
+            .with_required(&[1])
+            .with_forbidden(&[3]);
+        for (subset, complement) in generator.with_complement() {
+            assert!(subset.contains(&&1) && complement.contains(&&3));
+            assert_eq!(subset.len() + complement.len(), data.len());
+        }
+        let (subset, complement) = generator.with_complement().next().unwrap();
+        assert_eq!(subset, vec![&1]);
+        assert_eq!(complement, vec![&0, &2, &3, &4]);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::SubsetGenerator;
+use alloc::vec::Vec;
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Performs the compression step of iterative compression: given the
+    /// indices of a solution with `k` elements, searches for a solution with
+    /// `k - 1` elements that satisfies `is_valid`, and returns its elements in
+    /// the order of the dataset.
+    ///
+    /// The search branches over the elements of `solution` to keep, and fills
+    /// up the new solution with elements from outside it. Solutions that keep
+    /// more of the old solution are tried first, so one that is only a few
+    /// swaps away is found quickly. Without problem-specific pruning in
+    /// `is_valid`, all `C(n, k - 1)` candidates may be checked in the worst
+    /// case. The constraints of the generator are respected: every candidate
+    /// contains the required elements, and forbidden elements of `solution`
+    /// are never kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index of `solution` is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// // Vertex cover of the path 0 - 1 - 2 - 3.
+    /// let edges = [(0, 1), (1, 2), (2, 3)];
+    /// let vertices = vec![0, 1, 2, 3];
+    /// let covers = |s: &[&usize]| {
+    ///     edges.iter().all(|(u, v)| s.contains(&u) || s.contains(&v))
+    /// };
+    ///
+    /// let sg = SubsetGenerator::new(&vertices, false);
+    /// assert_eq!(sg.compress(&[0, 1, 2], covers), Some(vec![&0, &2]));
+    /// assert_eq!(sg.compress(&[1, 2], covers), None);
+    /// ```
+    pub fn compress<P>(&self, solution: &[usize], is_valid: P) -> Option<Vec<&'a T>>
+    where
+        P: Fn(&[&T]) -> bool,
+    {
+        let len = self.data.len();
+        let mut inside = solution.to_vec();
+        inside.sort_unstable();
+        inside.dedup();
+        if let Some(&last) = inside.last() {
+            assert!(last < len, "index {} is out of range", last);
+        }
+        let k = inside.len().checked_sub(1)?;
+
+        // Only the free elements are kept or added, besides the required ones.
+        let elements = self.free_elements();
+        let required = elements.required();
+        let free = k.checked_sub(required.len())?;
+        let is_free = |i: &usize| elements.bit(*i).is_some();
+        let outside: Vec<usize> = (0..len)
+            .filter(|i| is_free(i) && inside.binary_search(i).is_err())
+            .collect();
+        inside.retain(is_free);
+
+        let inside = SubsetGenerator::new(&inside, false);
+        let outside = SubsetGenerator::new(&outside, false);
+        let mut indices: Vec<usize> = Vec::with_capacity(k);
+        let mut candidate = Vec::with_capacity(k);
+        for kept in (0..=free).rev() {
+            for keep in inside.combinations(kept) {
+                for add in outside.combinations(free - kept) {
+                    indices.clear();
+                    indices.extend_from_slice(required);
+                    indices.extend(keep.iter().chain(add.iter()).copied());
+                    indices.sort_unstable();
+                    candidate.clear();
+                    candidate.extend(indices.iter().map(|&i| &self.data[i]));
+                    if is_valid(&candidate) {
+                        return Some(candidate);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+
+    #[test]
+    fn compress_finds_smaller_solution() {
+        let data: Vec<u32> = vec![8, 3, 5, 2, 7, 4];
+        let generator = SubsetGenerator::new(&data, false);
+        let heavy = |s: &[&u32]| s.iter().copied().sum::<u32>() >= 14;
+
+        // {8, 3, 5} can be compressed to {8, 7}, which swaps in 7.
+        let compressed = generator.compress(&[0, 1, 2], heavy).unwrap();
+        assert_eq!(compressed.len(), 2);
+        assert!(heavy(&compressed));
+        assert_eq!(generator.compress(&[0, 4], heavy), None);
+    }
+
+    #[test]
+    fn compress_prefers_few_swaps() {
+        let data: Vec<u32> = (0..8).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        let found = generator.compress(&[6, 2, 5, 2], |s| s.len() == 2);
+        assert_eq!(found, Some(vec![&2, &5]));
+        assert_eq!(generator.compress(&[3], |s| s.is_empty()), Some(vec![]));
+        assert_eq!(generator.compress(&[], |_| true), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn compress_out_of_range() {
+        let data = vec![1, 2, 3];
+        SubsetGenerator::new(&data, false).compress(&[3], |_| true);
+    }
+}
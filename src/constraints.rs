@@ -0,0 +1,827 @@
+use crate::mask::Mask;
+use crate::{SubsetError, SubsetGenerator};
+use alloc::vec;
+use alloc::vec::Vec;
+use bit_vec::BitVec;
+
+/// Restricts the enumeration to subsets that contain all the required indices
+/// and none of the forbidden ones. The cursor of an iterator only spans the
+/// free indices: bit `j` of the cursor selects the element at index `free[j]`,
+/// and the required elements are added to every subset. The free indices are
+/// increasing, unless an order of the indices was set, in which case `bits`
+/// maps every index of the dataset back to its bit of the cursor (if any).
+#[derive(Clone, Debug)]
+pub(crate) struct Constraints {
+    required: Vec<usize>,
+    forbidden: Vec<usize>,
+    free: Vec<usize>,
+    order: Option<Vec<usize>>,
+    bits: Vec<Option<usize>>,
+}
+
+impl Constraints {
+    fn new(len: usize) -> Constraints {
+        Constraints {
+            required: vec![],
+            forbidden: vec![],
+            free: (0..len).collect(),
+            order: None,
+            bits: vec![],
+        }
+    }
+
+    /// Assigns the bits of the cursor to the indices in the given order, which
+    /// must be a permutation of the indices of the dataset.
+    fn set_order(&mut self, order: &[usize]) {
+        self.order = Some(order.to_vec());
+        self.update_free(order.len());
+    }
+
+    /// Drops the order of the indices, so that the bits of the cursor are
+    /// assigned to the free indices in increasing order again.
+    pub(crate) fn clear_order(&mut self, len: usize) {
+        self.order = None;
+        self.update_free(len);
+    }
+
+    /// Recomputes the free indices, in the configured order, and the bits of
+    /// the indices if that order is not the natural one.
+    fn update_free(&mut self, len: usize) {
+        let (required, forbidden) = (&self.required, &self.forbidden);
+        let is_free = |i: &usize| !required.contains(i) && !forbidden.contains(i);
+        self.free = match &self.order {
+            Some(order) => order.iter().copied().filter(is_free).collect(),
+            None => (0..len).filter(is_free).collect(),
+        };
+        self.bits.clear();
+        if self.order.is_some() {
+            self.bits.resize(len, None);
+            for (j, &i) in self.free.iter().enumerate() {
+                self.bits[i] = Some(j);
+            }
+        }
+    }
+
+    /// Returns the bit of the cursor that selects the element at index `i` of
+    /// the dataset, or `None` if the element is not free.
+    fn bit(&self, i: usize) -> Option<usize> {
+        match &self.order {
+            Some(_) => self.bits[i],
+            None => self.free.binary_search(&i).ok(),
+        }
+    }
+
+    /// Adds `indices` to `list`, and recomputes the free indices.
+    fn add(&mut self, len: usize, indices: &[usize], required: bool) {
+        for &i in indices {
+            assert!(i < len, "index {} is out of range for {} elements", i, len);
+            let (list, other) = if required {
+                (&mut self.required, &self.forbidden)
+            } else {
+                (&mut self.forbidden, &self.required)
+            };
+            assert!(
+                !other.contains(&i),
+                "index {} is both required and forbidden",
+                i
+            );
+            if !list.contains(&i) {
+                list.push(i);
+            }
+        }
+        self.required.sort_unstable();
+        self.update_free(len);
+    }
+
+    /// Forbids every index that is not in `keep`, dropping it from the
+    /// required indices, and recomputes the free indices.
+    fn retain(&mut self, len: usize, keep: &[usize]) {
+        let mut kept = vec![false; len];
+        for &i in keep {
+            assert!(i < len, "index {} is out of range for {} elements", i, len);
+            kept[i] = true;
+        }
+        self.required.retain(|&i| kept[i]);
+        for (i, &kept) in kept.iter().enumerate() {
+            if !kept && !self.forbidden.contains(&i) {
+                self.forbidden.push(i);
+            }
+        }
+        self.update_free(len);
+    }
+
+    /// Returns true if any index is required, in which case the base subset is
+    /// not the empty set.
+    pub(crate) fn has_required(&self) -> bool {
+        !self.required.is_empty()
+    }
+
+    /// Returns the number of required indices, which every subset contains.
+    pub(crate) fn required_len(&self) -> usize {
+        self.required.len()
+    }
+
+    /// Returns the number of free indices, which is the length of the cursor.
+    pub(crate) fn free_len(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Returns the index in the dataset of the element selected by bit `j` of
+    /// the cursor.
+    pub(crate) fn position(&self, j: usize) -> usize {
+        self.free[j]
+    }
+
+    /// Returns true if the element at index `i` of the dataset is in the subset
+    /// selected by `mask`, or in the required elements if `mask` is `None`.
+    pub(crate) fn selects(&self, i: usize, mask: Option<&Mask>) -> bool {
+        if self.required.binary_search(&i).is_ok() {
+            return true;
+        }
+        match (self.bit(i), mask) {
+            (Some(j), Some(mask)) => mask.get(j),
+            _ => false,
+        }
+    }
+
+    /// Appends references to the required elements, and to the free elements
+    /// selected by `mask` (if any), to `buf`. The elements are appended in the
+    /// order of the dataset.
+    pub(crate) fn collect_into<'a, T>(
+        &self,
+        data: &'a [T],
+        mask: Option<&Mask>,
+        buf: &mut Vec<&'a T>,
+    ) {
+        if self.order.is_some() {
+            // The bits of the cursor are not in the order of the dataset.
+            let selected = (0..data.len()).filter(|&i| self.selects(i, mask));
+            buf.extend(selected.map(|i| &data[i]));
+            return;
+        }
+        let mut required = self.required.iter().copied().peekable();
+        if let Some(mask) = mask {
+            mask.for_each_one(|j| {
+                let i = self.free[j];
+                while let Some(r) = required.next_if(|&r| r < i) {
+                    buf.push(&data[r]);
+                }
+                buf.push(&data[i]);
+            });
+        }
+        for r in required {
+            buf.push(&data[r]);
+        }
+    }
+
+    /// Appends references to the free elements at the bits yielded by `bits`,
+    /// which must be increasing, to `buf`, together with the required elements
+    /// if `with_required` is set. The elements are appended in the order of
+    /// the dataset.
+    fn select_into<'a, T, I>(
+        &self,
+        data: &'a [T],
+        bits: I,
+        with_required: bool,
+        buf: &mut Vec<&'a T>,
+    ) where
+        I: IntoIterator<Item = usize>,
+    {
+        let required = self.required.iter().copied().filter(|_| with_required);
+        if self.order.is_some() {
+            // The bits are not in the order of the dataset.
+            let mut indices: Vec<usize> = bits.into_iter().map(|j| self.free[j]).collect();
+            indices.extend(required);
+            indices.sort_unstable();
+            buf.extend(indices.into_iter().map(|i| &data[i]));
+            return;
+        }
+        let mut required = required.peekable();
+        for j in bits {
+            let i = self.free[j];
+            while let Some(r) = required.next_if(|&r| r < i) {
+                buf.push(&data[r]);
+            }
+            buf.push(&data[i]);
+        }
+        buf.extend(required.map(|r| &data[r]));
+    }
+
+    /// Returns the bitmask over the whole dataset of the subset selected by
+    /// `mask`. The dataset must have at most 64 elements.
+    pub(crate) fn index(&self, mask: &Mask) -> u64 {
+        let mut index = 0;
+        for &r in &self.required {
+            index |= 1 << r;
+        }
+        mask.for_each_one(|j| index |= 1 << self.free[j]);
+        index
+    }
+}
+
+/// The elements that the iterators with their own order enumerate, so that
+/// they respect the constraints of the generator: bit `j` of their subsets
+/// selects the `j`-th free element, in the order set by
+/// [`with_order`](SubsetGenerator::with_order), and the required elements are
+/// added to every subset. Without constraints, bit `j` simply selects
+/// `data[j]`.
+pub(crate) struct FreeElements<'a, T> {
+    data: &'a [T],
+    constraints: Option<Constraints>,
+}
+
+impl<'a, T> Clone for FreeElements<'a, T> {
+    fn clone(&self) -> Self {
+        FreeElements {
+            data: self.data,
+            constraints: self.constraints.clone(),
+        }
+    }
+}
+
+impl<'a, T> FreeElements<'a, T> {
+    /// Returns the elements of `data` without any constraints.
+    pub(crate) fn new(data: &'a [T]) -> FreeElements<'a, T> {
+        FreeElements {
+            data,
+            constraints: None,
+        }
+    }
+
+    /// Returns true if there are no constraints, so bit `j` selects `data[j]`.
+    pub(crate) fn is_unconstrained(&self) -> bool {
+        self.constraints.is_none()
+    }
+
+    /// Returns the number of free elements.
+    pub(crate) fn len(&self) -> usize {
+        match &self.constraints {
+            Some(constraints) => constraints.free_len(),
+            None => self.data.len(),
+        }
+    }
+
+    /// Returns the number of required elements, which every subset contains.
+    pub(crate) fn required_len(&self) -> usize {
+        self.constraints
+            .as_ref()
+            .map_or(0, Constraints::required_len)
+    }
+
+    /// Returns the indices of the required elements, in increasing order.
+    pub(crate) fn required(&self) -> &[usize] {
+        match &self.constraints {
+            Some(constraints) => &constraints.required,
+            None => &[],
+        }
+    }
+
+    /// Returns the index in the dataset of the element selected by bit `j`.
+    pub(crate) fn index(&self, j: usize) -> usize {
+        match &self.constraints {
+            Some(constraints) => constraints.position(j),
+            None => j,
+        }
+    }
+
+    /// Returns the element selected by bit `j`.
+    pub(crate) fn get(&self, j: usize) -> &'a T {
+        &self.data[self.index(j)]
+    }
+
+    /// Returns the bit that selects the element at index `i` of the dataset,
+    /// or `None` if the element is required or forbidden.
+    pub(crate) fn bit(&self, i: usize) -> Option<usize> {
+        match &self.constraints {
+            Some(constraints) => constraints.bit(i),
+            None => Some(i),
+        }
+    }
+
+    /// Appends references to the required elements, and to the free elements
+    /// at the bits yielded by `bits`, which must be increasing, to `buf`. The
+    /// elements are appended in the order of the dataset.
+    pub(crate) fn collect_into<I>(&self, bits: I, buf: &mut Vec<&'a T>)
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        self.select_into(bits, true, buf);
+    }
+
+    /// Returns the required elements together with the free elements at the
+    /// bits yielded by `bits`, like [`collect_into`](Self::collect_into).
+    pub(crate) fn collect<I>(&self, bits: I) -> Vec<&'a T>
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let mut result = Vec::new();
+        self.collect_into(bits, &mut result);
+        result
+    }
+
+    /// Returns the required elements together with the free elements whose
+    /// bits are set in `set`.
+    pub(crate) fn subset(&self, set: &BitVec) -> Vec<&'a T> {
+        let bits = set.iter().enumerate().filter(|&(_, bit)| bit);
+        self.collect(bits.map(|(j, _)| j))
+    }
+
+    /// Like [`collect_into`](Self::collect_into), but leaves out the required
+    /// elements if `with_required` is false.
+    pub(crate) fn select_into<I>(&self, bits: I, with_required: bool, buf: &mut Vec<&'a T>)
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        match &self.constraints {
+            Some(constraints) => constraints.select_into(self.data, bits, with_required, buf),
+            None => buf.extend(bits.into_iter().map(|i| &self.data[i])),
+        }
+    }
+
+    /// Returns the mask over the whole dataset of the required elements and
+    /// the free elements whose bits are set in `set`.
+    pub(crate) fn dataset_mask(&self, set: &BitVec) -> BitVec {
+        match &self.constraints {
+            Some(constraints) => BitVec::from_fn(self.data.len(), |i| {
+                constraints.required.binary_search(&i).is_ok()
+                    || constraints.bit(i).is_some_and(|j| set[j])
+            }),
+            None => set.clone(),
+        }
+    }
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Restricts the enumeration to subsets that contain all the elements at
+    /// the given indices. Only the remaining free elements are enumerated, so
+    /// each required index halves the number of subsets. The subset consisting
+    /// of just the required elements is reported even if the generator was
+    /// configured without the empty set, as long as it is not empty itself.
+    ///
+    /// The constraints apply to all the iterators and searches; the indices
+    /// used by `seek` and `split` then range over the free elements only.
+    /// Those with their own order, such as `gray_iter` and `prune_iter`, run
+    /// over the free elements and add the required elements to every subset,
+    /// and the sizes passed to `combinations` and its relatives count the
+    /// required elements too.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is out of range, or if it was already forbidden.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false).with_required(&[1]);
+    /// let subsets: Vec<Vec<&i32>> = sg.iter().collect();
+    /// assert_eq!(subsets, vec![vec![&2], vec![&1, &2], vec![&2, &3], vec![&1, &2, &3]]);
+    /// ```
+    pub fn with_required(mut self, indices: &[usize]) -> Self {
+        let len = self.data.len();
+        self.constraints
+            .get_or_insert_with(|| Constraints::new(len))
+            .add(len, indices, true);
+        self
+    }
+
+    /// Restricts the enumeration to subsets that contain none of the elements
+    /// at the given indices. Like
+    /// [`with_required`](SubsetGenerator::with_required), this applies to all
+    /// the iterators and searches.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is out of range, or if it was already required.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3, 4];
+    /// let sg = SubsetGenerator::new(&data, false)
+    ///     .with_required(&[0])
+    ///     .with_forbidden(&[2, 3]);
+    /// let subsets: Vec<Vec<&i32>> = sg.iter().collect();
+    /// assert_eq!(subsets, vec![vec![&1], vec![&1, &2]]);
+    /// ```
+    pub fn with_forbidden(mut self, indices: &[usize]) -> Self {
+        let len = self.data.len();
+        self.constraints
+            .get_or_insert_with(|| Constraints::new(len))
+            .add(len, indices, false);
+        self
+    }
+
+    /// Restricts all future enumeration to the elements at the indices in
+    /// `keep`, as if the dataset had shrunk to just those elements, without
+    /// copying the data. This is useful when a dataset is reduced between
+    /// phases, such as after kernelization. Every other element is forbidden,
+    /// and dropped from the required elements, so without required elements
+    /// the generator enumerates the `2^|keep|` subsets of the kept elements
+    /// (minus the empty set, if excluded), in every iterator and search. The indices still refer to the
+    /// original dataset, and the elements of each subset are listed in its
+    /// order. Retaining again keeps the intersection, as elements that were
+    /// removed before are not restored.
+    ///
+    /// An order set with [`with_order`](SubsetGenerator::with_order) is kept:
+    /// the bits of the cursor are assigned to the kept elements in the order in
+    /// which they appear in the permutation. As the cursor only spans the kept
+    /// elements, the indices used by `seek` and `split` change meaning, so
+    /// positions recorded before retaining do not carry over.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3, 4];
+    /// let mut sg = SubsetGenerator::new(&data, false);
+    /// assert_eq!(sg.iter().count(), 15);
+    ///
+    /// sg.retain_indices(&[0, 3]);
+    /// let subsets: Vec<Vec<&i32>> = sg.iter().collect();
+    /// assert_eq!(subsets, vec![vec![&1], vec![&4], vec![&1, &4]]);
+    /// ```
+    pub fn retain_indices(&mut self, keep: &[usize]) {
+        let len = self.data.len();
+        self.constraints
+            .get_or_insert_with(|| Constraints::new(len))
+            .retain(len, keep);
+    }
+
+    /// Returns a generator that assigns the bits of the cursor to the elements
+    /// in the order of `perm`: the lowest bit selects `data[perm[0]]`, the next
+    /// one `data[perm[1]]`, and so on. The first elements of `perm` are thus
+    /// toggled most often and are the first to be enumerated, which lets a
+    /// search branch on the most relevant elements first. The elements of each
+    /// subset are still listed in the order of the dataset.
+    ///
+    /// The order applies to all the iterators and searches that enumerate the
+    /// bits of a cursor, and the indices used by `seek` and `split` refer to
+    /// the bits in this order. Required and
+    /// forbidden elements are skipped, so their position in `perm` does not
+    /// matter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an index of `perm` is out of range, or if `perm` is
+    /// not a permutation of the indices of the dataset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::{SubsetError, SubsetGenerator};
+    ///
+    /// let data = vec!['a', 'b', 'c'];
+    /// let sg = SubsetGenerator::new(&data, false).with_order(&[2, 0, 1]).unwrap();
+    /// let mut iter = sg.iter();
+    ///
+    /// assert_eq!(iter.next(), Some(vec![&'c']));
+    /// assert_eq!(iter.next(), Some(vec![&'a']));
+    /// assert_eq!(iter.next(), Some(vec![&'a', &'c']));
+    /// assert_eq!(iter.next(), Some(vec![&'b']));
+    ///
+    /// let result = SubsetGenerator::new(&data, false).with_order(&[0, 1, 1]);
+    /// assert_eq!(result.unwrap_err(), SubsetError::NotAPermutation);
+    /// ```
+    pub fn with_order(&self, perm: &[usize]) -> Result<Self, SubsetError> {
+        let len = self.data.len();
+        let mut seen = vec![false; len];
+        for &index in perm {
+            if index >= len {
+                return Err(SubsetError::IndexOutOfRange { index, len });
+            }
+            if core::mem::replace(&mut seen[index], true) {
+                return Err(SubsetError::NotAPermutation);
+            }
+        }
+        if perm.len() != len {
+            return Err(SubsetError::NotAPermutation);
+        }
+
+        let mut generator = self.clone();
+        generator
+            .constraints
+            .get_or_insert_with(|| Constraints::new(len))
+            .set_order(perm);
+        Ok(generator)
+    }
+
+    /// Returns the number of elements that are enumerated, which is the
+    /// length of the cursor of the iterators.
+    pub(crate) fn free_len(&self) -> usize {
+        match &self.constraints {
+            Some(constraints) => constraints.free_len(),
+            None => self.data.len(),
+        }
+    }
+
+    /// Returns the number of required elements, which every subset contains.
+    pub(crate) fn required_len(&self) -> usize {
+        self.constraints
+            .as_ref()
+            .map_or(0, Constraints::required_len)
+    }
+
+    /// Returns the free elements, over which the iterators with their own
+    /// order enumerate the subsets.
+    pub(crate) fn free_elements(&self) -> FreeElements<'a, T> {
+        FreeElements {
+            data: self.data,
+            constraints: self.constraints.clone(),
+        }
+    }
+
+    /// Returns true if the subset without any free elements is reported. This
+    /// is the empty set, unless there are required elements.
+    pub(crate) fn includes_base(&self) -> bool {
+        self.with_emptyset
+            || self
+                .constraints
+                .as_ref()
+                .is_some_and(Constraints::has_required)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Prune, SubsetError, SubsetGenerator, Toggle};
+    use bit_vec::BitVec;
+
+    #[test]
+    fn constraints_match_filter() {
+        let data: Vec<usize> = (0..8).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let constrained = SubsetGenerator::new(&data, with_emptyset)
+                .with_required(&[5, 1])
+                .with_forbidden(&[2])
+                .with_forbidden(&[7, 2]);
+            let expected: Vec<_> = generator
+                .iter()
+                .filter(|s| s.contains(&&1) && s.contains(&&5))
+                .filter(|s| !s.contains(&&2) && !s.contains(&&7))
+                .collect();
+            let mut iter = constrained.iter();
+            assert_eq!(iter.len(), 16);
+            let mut subsets: Vec<_> = iter.by_ref().collect();
+            assert!(subsets.windows(2).all(|w| w[0] != w[1]));
+            subsets.sort();
+            let mut expected = expected;
+            expected.sort();
+            assert_eq!(subsets, expected);
+        }
+    }
+
+    #[test]
+    fn constraints_in_derived_iterators() {
+        let data: Vec<usize> = (0..6).collect();
+        let generator = SubsetGenerator::new(&data, true)
+            .with_required(&[0])
+            .with_forbidden(&[3]);
+        let expected: Vec<_> = generator.iter().collect();
+
+        let mut backward: Vec<_> = generator.iter().rev().collect();
+        backward.reverse();
+        assert_eq!(backward, expected);
+
+        let split: Vec<_> = generator.split(3).into_iter().flatten().collect();
+        assert_eq!(split, expected);
+
+        let masks: Vec<u64> = generator.masked_iter().map(|(mask, _)| mask).collect();
+        assert!(masks.iter().all(|mask| mask & 1 == 1 && mask & 8 == 0));
+        assert_eq!(masks[0], 1);
+
+        let mut iter = generator.iter();
+        iter.next();
+        let delta = iter.next_delta().unwrap();
+        assert_eq!(delta.subset, vec![&0, &1]);
+        assert_eq!(delta.added, vec![&1]);
+    }
+
+    #[test]
+    fn constraints_in_specialized_iterators() {
+        fn sorted<'a>(subsets: impl Iterator<Item = Vec<&'a usize>>) -> Vec<Vec<&'a usize>> {
+            let mut subsets: Vec<_> = subsets.collect();
+            subsets.sort();
+            subsets
+        }
+
+        let data: Vec<usize> = (0..7).collect();
+        for &with_emptyset in &[false, true] {
+            let unordered = SubsetGenerator::new(&data, with_emptyset)
+                .with_required(&[5, 1])
+                .with_forbidden(&[2]);
+            let ordered = unordered
+                .clone()
+                .with_order(&[6, 2, 0, 5, 4, 3, 1])
+                .unwrap();
+            for generator in &[unordered, ordered] {
+                let expected = sorted(generator.iter());
+                assert_eq!(expected.len(), 16);
+                let of_len = |keep: &dyn Fn(usize) -> bool| {
+                    let subsets = expected.iter().filter(|s| keep(s.len()));
+                    subsets.cloned().collect::<Vec<_>>()
+                };
+
+                assert_eq!(sorted(generator.gray_iter()), expected);
+                assert_eq!(sorted(generator.lex_iter()), expected);
+                assert_eq!(sorted(generator.prune_iter(|_| Prune::Keep)), expected);
+                assert_eq!(sorted(generator.by_weight(|&x| x as u64)), expected);
+                assert_eq!(sorted(generator.dedup_iter(|&x| x)), expected);
+                assert_eq!(sorted(generator.independent_iter(&[])), expected);
+                let all = BitVec::from_elem(data.len(), true);
+                assert_eq!(sorted(generator.submasks_of(&all)), expected);
+                let layers = generator.by_cardinality().flat_map(|(_, layer)| layer);
+                assert_eq!(sorted(layers), expected);
+                assert_eq!(sorted(generator.layers().flatten()), expected);
+
+                for k in 0..=data.len() {
+                    let combinations = sorted(generator.combinations(k));
+                    assert_eq!(combinations, of_len(&|len| len == k), "k = {}", k);
+                    let at_least = sorted(generator.at_least(k));
+                    assert_eq!(at_least, of_len(&|len| len >= k), "k = {}", k);
+                }
+                let bounded = sorted(generator.bounded(3, 5));
+                assert_eq!(bounded, of_len(&|len| (3..=5).contains(&len)));
+                let maximal = sorted(generator.maximal(|s| s.len() <= 4));
+                assert_eq!(maximal, of_len(&|len| len == 4));
+
+                // The required elements conflict with 0, and 2 is forbidden.
+                let independent = sorted(generator.independent_iter(&[(1, 0), (2, 3)]));
+                let without_zero = expected.iter().filter(|s| !s.contains(&&0));
+                assert_eq!(independent, without_zero.cloned().collect::<Vec<_>>());
+                assert_eq!(generator.independent_iter(&[(5, 1)]).count(), 0);
+
+                let mut iter = generator.gray_iter();
+                while let Some(toggle) = iter.next_toggle() {
+                    let i = match toggle {
+                        Toggle::Added(i) => i,
+                        Toggle::Removed(i) => i,
+                    };
+                    assert!(![1, 2, 5].contains(&i));
+                }
+                let (sum, mask) = generator.optimize_incremental(
+                    0,
+                    |sum, &x| *sum += x + 1,
+                    |sum, &x| *sum -= x + 1,
+                    |a, b| a > b,
+                );
+                assert_eq!(sum, 25);
+                assert!(mask.eq_vec(&[true, true, false, true, true, true, true]));
+
+                for target in 0..25 {
+                    let sum = |s: &[&usize]| s.iter().copied().sum::<usize>();
+                    let exists = expected.iter().any(|s| sum(s) == target);
+                    let found = generator.subset_sum(target as i64, |&x| x as i64);
+                    assert_eq!(found.is_some(), exists, "target {}", target);
+                    if let Some(subset) = found {
+                        assert!(expected.contains(&subset));
+                    }
+                    let found = generator.meet_in_middle(sum, |a, b| (a + b).cmp(&target));
+                    assert!(found.map_or(!exists, |s| expected.contains(&s)));
+                }
+
+                let compressed = generator.compress(&[0, 1, 2, 5], |_| true);
+                assert_eq!(compressed, Some(vec![&0, &1, &5]));
+                assert_eq!(generator.compress(&[1, 2], |_| true), None);
+
+                let multisets: Vec<_> = generator.with_replacement(3).collect();
+                assert_eq!(multisets.len(), 6);
+                assert!(multisets
+                    .iter()
+                    .all(|s| s.contains(&&1) && s.contains(&&5) && !s.contains(&&2)));
+                assert_eq!(generator.with_replacement(1).count(), 0);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn conflicting_constraints() {
+        let data = vec![1, 2, 3];
+        SubsetGenerator::new(&data, false)
+            .with_required(&[1])
+            .with_forbidden(&[1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_range_constraint() {
+        let data = vec![1, 2, 3];
+        SubsetGenerator::new(&data, false).with_forbidden(&[3]);
+    }
+
+    #[test]
+    fn order_permutes_bits() {
+        let data: Vec<usize> = (0..6).collect();
+        let perm = [4, 1, 5, 0, 3, 2];
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let ordered = generator.with_order(&perm).unwrap();
+            for (index, subset) in ordered.indexed_iter() {
+                let expected: Vec<_> = (0..6)
+                    .filter(|&j| index >> j & 1 == 1)
+                    .map(|j| perm[j])
+                    .collect();
+                let mut expected: Vec<_> = expected.iter().map(|&i| &data[i]).collect();
+                expected.sort();
+                assert_eq!(subset, expected);
+            }
+            assert_eq!(ordered.iter().len(), generator.iter().len());
+
+            // Constraints skip their elements, in either order of the calls.
+            let constrained = ordered.clone().with_required(&[5]).with_forbidden(&[1]);
+            let expected: Vec<_> = ordered
+                .iter()
+                .filter(|s| s.contains(&&5) && !s.contains(&&1))
+                .collect();
+            assert_eq!(constrained.iter().collect::<Vec<_>>(), expected);
+            let reordered = generator
+                .clone()
+                .with_required(&[5])
+                .with_forbidden(&[1])
+                .with_order(&perm)
+                .unwrap();
+            assert_eq!(reordered.iter().collect::<Vec<_>>(), expected);
+            let mut backward: Vec<_> = reordered.iter().rev().collect();
+            backward.reverse();
+            assert_eq!(backward, expected);
+        }
+    }
+
+    #[test]
+    fn retain_indices_shrinks_dataset() {
+        let data: Vec<usize> = (0..8).collect();
+        for &with_emptyset in &[false, true] {
+            let mut generator = SubsetGenerator::new(&data, with_emptyset);
+            assert_eq!(generator.iter().count(), 256 - !with_emptyset as usize);
+
+            let keep = [6, 1, 4, 3];
+            generator.retain_indices(&keep);
+            let kept: Vec<usize> = vec![1, 3, 4, 6];
+            let expected: Vec<Vec<&usize>> =
+                SubsetGenerator::new(&kept, with_emptyset).iter().collect();
+            assert_eq!(generator.iter().collect::<Vec<_>>(), expected);
+            assert_eq!(generator.iter().len(), 16 - !with_emptyset as usize);
+
+            // The iterators with their own order only see the kept elements.
+            let shrunk = SubsetGenerator::new(&kept, with_emptyset);
+            for k in 0..=data.len() {
+                assert!(generator.combinations(k).eq(shrunk.combinations(k)));
+            }
+            assert!(generator.gray_iter().eq(shrunk.gray_iter()));
+            assert!(generator.lex_iter().eq(shrunk.lex_iter()));
+
+            // Retaining again intersects with the kept elements.
+            generator.retain_indices(&[0, 1, 6]);
+            let subsets: Vec<_> = generator.iter().collect();
+            let mut expected = vec![vec![&1], vec![&6], vec![&1, &6]];
+            if with_emptyset {
+                expected.insert(0, vec![]);
+            }
+            assert_eq!(subsets, expected);
+        }
+    }
+
+    #[test]
+    fn retain_indices_with_constraints() {
+        let data: Vec<char> = "abcdef".chars().collect();
+        let mut generator = SubsetGenerator::new(&data, false)
+            .with_required(&[0, 5])
+            .with_forbidden(&[2])
+            .with_order(&[4, 3, 2, 1, 0, 5])
+            .unwrap();
+        generator.retain_indices(&[0, 1, 2, 4]);
+
+        // 'f' is no longer required, 'c' stays forbidden, and the bits follow
+        // the order: 'e' first, then 'b'.
+        let subsets: Vec<String> = generator.iter().map(|s| s.into_iter().collect()).collect();
+        assert_eq!(subsets, vec!["a", "ae", "ab", "abe"]);
+    }
+
+    #[test]
+    fn order_rejects_non_permutations() {
+        let data = vec![1, 2, 3];
+        let generator = SubsetGenerator::new(&data, false);
+        assert_eq!(
+            generator.with_order(&[0, 3, 1]).unwrap_err(),
+            SubsetError::IndexOutOfRange { index: 3, len: 3 }
+        );
+        for perm in &[&[0, 1][..], &[0, 1, 2, 0], &[2, 2, 1]] {
+            assert_eq!(
+                generator.with_order(perm).unwrap_err(),
+                SubsetError::NotAPermutation
+            );
+        }
+        assert!(generator.with_order(&[1, 2, 0]).is_ok());
+    }
+}
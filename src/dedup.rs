@@ -0,0 +1,214 @@
+use crate::constraints::FreeElements;
+use crate::SubsetGenerator;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::hash::Hash;
+use core::iter::FusedIterator;
+use std::collections::HashMap;
+
+/// Iterator over the distinct subsets of a dataset with duplicate elements,
+/// created by [`SubsetGenerator::dedup_iter`]. Only available with the `std`
+/// feature.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 1, 2];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// assert_eq!(sg.dedup_iter(|&x| x).count(), 5);
+/// ```
+pub struct DedupSubsetIter<'a, T> {
+    elements: FreeElements<'a, T>,
+    group: Vec<usize>,
+    rank: Vec<usize>,
+    sizes: Vec<usize>,
+    counts: Vec<usize>,
+    with_emptyset: bool,
+    first: bool,
+    exhausted: bool,
+    remaining: Option<usize>,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over the distinct subsets of the dataset, where two
+    /// subsets are equal if they hold the same multiset of keys. Equal
+    /// elements make the plain enumeration report the same subset by value
+    /// several times: `[1, 1, 2]` has 8 subsets, but only 6 distinct ones.
+    ///
+    /// The elements are grouped by their key, and the iterator enumerates how
+    /// many elements to take from each group, like a mixed-radix counter in
+    /// which the group of the first element counts fastest. Of a group, the
+    /// first elements in the order of the dataset are taken, and the elements
+    /// of each subset are listed in the order of the dataset. With groups of
+    /// `m_1, ..., m_g` elements, this reports `(m_1 + 1) * ... * (m_g + 1)`
+    /// subsets (minus the empty set, if excluded) instead of `2^n`, each in
+    /// `O(n)` time after grouping the keys once in `O(n)`. Without duplicates
+    /// this is exactly the full enumeration, in the same order as `iter`.
+    ///
+    /// The constraints of the generator are respected: only the free elements
+    /// are grouped, in the order set by
+    /// [`with_order`](Self::with_order), and the required elements are in
+    /// every subset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec!["a", "b", "a"];
+    /// let sg = SubsetGenerator::new(&data, true);
+    /// let mut iter = sg.dedup_iter(|&s| s);
+    ///
+    /// assert_eq!(iter.next(), Some(vec![]));
+    /// assert_eq!(iter.next(), Some(vec![&"a"]));
+    /// assert_eq!(iter.next(), Some(vec![&"a", &"a"]));
+    /// assert_eq!(iter.next(), Some(vec![&"b"]));
+    /// assert_eq!(iter.count(), 2);
+    /// ```
+    pub fn dedup_iter<K, F>(&self, key: F) -> DedupSubsetIter<'a, T>
+    where
+        K: Eq + Hash,
+        F: Fn(&T) -> K,
+    {
+        let elements = self.free_elements();
+        let mut groups = HashMap::new();
+        let mut group = Vec::with_capacity(elements.len());
+        let mut rank = Vec::with_capacity(elements.len());
+        let mut sizes = vec![];
+        for x in (0..elements.len()).map(|j| elements.get(j)) {
+            let next = groups.len();
+            let g = *groups.entry(key(x)).or_insert(next);
+            if g == sizes.len() {
+                sizes.push(0);
+            }
+            group.push(g);
+            rank.push(sizes[g]);
+            sizes[g] += 1;
+        }
+
+        let total = sizes
+            .iter()
+            .try_fold(1usize, |total, &size| total.checked_mul(size + 1));
+        let with_emptyset = self.includes_base();
+        DedupSubsetIter {
+            elements,
+            group,
+            rank,
+            counts: vec![0; sizes.len()],
+            sizes,
+            with_emptyset,
+            first: true,
+            exhausted: false,
+            remaining: total.map(|total| total - !with_emptyset as usize),
+        }
+    }
+}
+
+impl<'a, T> DedupSubsetIter<'a, T> {
+    /// Takes one more element of the first group that has any left, and none
+    /// of the groups before it. Returns false if all the elements were taken.
+    fn increment(&mut self) -> bool {
+        for (count, &size) in self.counts.iter_mut().zip(&self.sizes) {
+            if *count < size {
+                *count += 1;
+                return true;
+            }
+            *count = 0;
+        }
+        false
+    }
+
+    fn subset(&self) -> Vec<&'a T> {
+        let bits = (0..self.elements.len()).filter(|&j| self.rank[j] < self.counts[self.group[j]]);
+        self.elements.collect(bits)
+    }
+}
+
+impl<'a, T> Iterator for DedupSubsetIter<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+        if self.first {
+            self.first = false;
+            if self.with_emptyset {
+                self.remaining = self.remaining.map(|remaining| remaining - 1);
+                return Some(self.elements.collect(None));
+            }
+        }
+        if !self.increment() {
+            self.exhausted = true;
+            return None;
+        }
+        self.remaining = self.remaining.map(|remaining| remaining - 1);
+        Some(self.subset())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining {
+            Some(remaining) => (remaining, Some(remaining)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+impl<'a, T> FusedIterator for DedupSubsetIter<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+
+    #[test]
+    fn dedup_reduces_count() {
+        let data = vec![3, 1, 3, 2, 1, 3];
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let subsets: Vec<_> = generator.dedup_iter(|&x| x).collect();
+            // Three 3s, two 1s, and one 2.
+            assert_eq!(subsets.len(), 4 * 3 * 2 - !with_emptyset as usize);
+            assert!(subsets.len() < generator.iter().count());
+
+            // The distinct subsets by value are exactly those of `iter`.
+            let sorted = |s: &Vec<&i32>| {
+                let mut s: Vec<i32> = s.iter().copied().copied().collect();
+                s.sort();
+                s
+            };
+            let mut expected: Vec<_> = generator.iter().map(|s| sorted(&s)).collect();
+            expected.sort();
+            expected.dedup();
+            let mut distinct: Vec<_> = subsets.iter().map(sorted).collect();
+            distinct.sort();
+            assert_eq!(distinct, expected);
+        }
+    }
+
+    #[test]
+    fn dedup_without_duplicates_is_iter() {
+        let data: Vec<usize> = (0..7).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let mut iter = generator.dedup_iter(|&x| x);
+            assert_eq!(iter.size_hint(), generator.iter().size_hint());
+            assert!(iter.by_ref().eq(generator.iter()));
+            assert_eq!(iter.size_hint(), (0, Some(0)));
+            assert_eq!(iter.next(), None);
+        }
+
+        let empty: Vec<usize> = vec![];
+        let generator = SubsetGenerator::new(&empty, true);
+        assert_eq!(generator.dedup_iter(|&x| x).count(), 1);
+    }
+
+    #[test]
+    fn dedup_by_key() {
+        let data = vec!["apple", "avocado", "banana", "blueberry", "cherry"];
+        let generator = SubsetGenerator::new(&data, false);
+        let first_letters = generator.dedup_iter(|s| s.as_bytes()[0]);
+        assert_eq!(first_letters.count(), 3 * 3 * 2 - 1);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::SubsetIter;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// A subset together with the elements that changed with respect to the
+/// previously reported subset, as returned by [`SubsetIter::next_delta`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubsetDelta<'a, T> {
+    /// The elements of the new subset.
+    pub subset: Vec<&'a T>,
+    /// The elements that are in the new subset, but not in the previous one.
+    pub added: Vec<&'a T>,
+    /// The elements that were in the previous subset, but not in the new one.
+    pub removed: Vec<&'a T>,
+}
+
+impl<'a, T> SubsetIter<'a, T> {
+    /// Advances the iterator like `next`, but also reports which elements were
+    /// added and removed relative to the previously reported subset. If no
+    /// subset was reported just before, because the iterator was only created
+    /// or rewound, repositioned with `seek`, `advance_by` or
+    /// `next_with_popcount`, or last reported a subset from the back, the new
+    /// subset is compared with the empty set: `added` holds the full subset
+    /// and `removed` is empty. This also holds for the first subset of the
+    /// iterators returned by `split`, `resume` and `resume_from`, and for the
+    /// base subset (the empty set, or just the required elements).
+    ///
+    /// Incrementing the cursor clears its trailing set bits and sets the bit
+    /// just above them, so the new subset gains exactly one element and loses
+    /// all elements before it. Callers can use this to update an objective
+    /// function instead of recomputing it from scratch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.iter();
+    ///
+    /// let delta = iter.next_delta().unwrap();
+    /// assert_eq!(delta.added, vec![&1]);
+    /// iter.next_delta();
+    /// let delta = iter.next_delta().unwrap();
+    /// assert_eq!(delta.subset, vec![&1, &2]);
+    /// assert_eq!(delta.added, vec![&1]);
+    /// assert!(delta.removed.is_empty());
+    ///
+    /// let delta = iter.next_delta().unwrap();
+    /// assert_eq!(delta.subset, vec![&3]);
+    /// assert_eq!(delta.added, vec![&3]);
+    /// assert_eq!(delta.removed, vec![&1, &2]);
+    /// ```
+    pub fn next_delta(&mut self) -> Option<SubsetDelta<'a, T>> {
+        let reported = self.reported;
+        self.reported = true;
+        if self.with_emptyset {
+            self.with_emptyset = false;
+            let mut subset = Vec::new();
+            self.select_into(None, &mut subset);
+            return Some(SubsetDelta {
+                added: subset.clone(),
+                subset,
+                removed: vec![],
+            });
+        }
+
+        if !self.next_set() {
+            return None;
+        }
+        if !reported {
+            let subset = self.subset();
+            return Some(SubsetDelta {
+                added: subset.clone(),
+                subset,
+                removed: vec![],
+            });
+        }
+
+        let lowest = self.set.lowest_set()?;
+        Some(SubsetDelta {
+            subset: self.subset(),
+            added: vec![self.element(lowest)],
+            removed: (0..lowest).map(|bit| self.element(bit)).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+    use std::collections::HashSet;
+
+    #[test]
+    fn delta_reconstructs_subsets() {
+        let data: Vec<usize> = (0..6).collect();
+        let generator = SubsetGenerator::new(&data, true);
+        let mut iter = generator.iter();
+        let mut current: HashSet<usize> = HashSet::new();
+        let mut iters = 0;
+        while let Some(delta) = iter.next_delta() {
+            for x in delta.removed {
+                assert!(current.remove(x));
+            }
+            for x in delta.added {
+                assert!(current.insert(*x));
+            }
+            let expected: HashSet<usize> = delta.subset.into_iter().copied().collect();
+            assert_eq!(current, expected);
+            iters += 1;
+        }
+        assert_eq!(iters, 64);
+    }
+
+    #[test]
+    fn delta_first_item() {
+        let data = vec![1, 2];
+        let generator = SubsetGenerator::new(&data, true);
+        let mut iter = generator.iter();
+        let first = iter.next_delta().unwrap();
+        assert!(first.subset.is_empty() && first.added.is_empty() && first.removed.is_empty());
+        let second = iter.next_delta().unwrap();
+        assert_eq!(second.added, second.subset);
+        assert!(second.removed.is_empty());
+    }
+
+    #[test]
+    fn delta_after_repositioning() {
+        let data = vec![1, 2, 3];
+        let generator = SubsetGenerator::new(&data, false);
+        // The second chunk starts at index 4, and the subset at index 3 was
+        // never reported by it.
+        let mut chunk = generator.split(2).pop().unwrap();
+        let delta = chunk.next_delta().unwrap();
+        assert_eq!(delta.subset, vec![&3]);
+        assert_eq!(delta.added, vec![&3]);
+        assert!(delta.removed.is_empty());
+        let delta = chunk.next_delta().unwrap();
+        assert_eq!(delta.added, vec![&1]);
+        assert!(delta.removed.is_empty());
+
+        let mut iter = generator.iter();
+        assert_eq!(iter.advance_by(2), 2);
+        let delta = iter.next_delta().unwrap();
+        assert_eq!(delta.subset, vec![&1, &2]);
+        assert_eq!(delta.added, vec![&1, &2]);
+        assert!(delta.removed.is_empty());
+
+        iter.seek(2);
+        let delta = iter.next_delta().unwrap();
+        assert_eq!((delta.added, delta.removed), (vec![&1, &2], vec![]));
+        iter.next_with_popcount(1);
+        let delta = iter.next_delta().unwrap();
+        assert_eq!(delta.subset, vec![&1, &3]);
+        assert_eq!((delta.added, delta.removed), (vec![&1, &3], vec![]));
+        iter.next_back();
+        let delta = iter.next_delta().unwrap();
+        assert_eq!((delta.added, delta.removed), (vec![&2, &3], vec![]));
+
+        let mut resumed = generator.resume(generator.iter().save_state());
+        let delta = resumed.next_delta().unwrap();
+        assert_eq!((delta.added, delta.removed), (vec![&1], vec![]));
+        resumed.next();
+        let delta = resumed.next_delta().unwrap();
+        assert_eq!((delta.added, delta.removed), (vec![&1], vec![]));
+        let delta = resumed.next_delta().unwrap();
+        assert_eq!((delta.added, delta.removed), (vec![&3], vec![&1, &2]));
+    }
+}
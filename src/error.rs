@@ -0,0 +1,84 @@
+use crate::Order;
+use core::fmt;
+
+/// The error returned when a generator cannot be configured as requested.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubsetError {
+    /// An index does not refer to an element of the dataset of `len` elements.
+    IndexOutOfRange {
+        /// The offending index.
+        index: usize,
+        /// The number of elements in the dataset.
+        len: usize,
+    },
+    /// An index is both required and forbidden.
+    Conflict {
+        /// The offending index.
+        index: usize,
+    },
+    /// The size range is empty, as `min > max`.
+    EmptySizeRange {
+        /// The lower bound of the range.
+        min: usize,
+        /// The upper bound of the range.
+        max: usize,
+    },
+    /// The dataset has `n` elements, more than the `max` that the index-based
+    /// operations support.
+    TooLarge {
+        /// The number of elements in the dataset.
+        n: usize,
+        /// The largest supported number of elements.
+        max: usize,
+    },
+    /// Required or forbidden indices were combined with an order that does not
+    /// support them; only [`Order::Binary`] does.
+    UnsupportedOrder(Order),
+    /// The order of the elements is not a permutation of the indices of the
+    /// dataset.
+    NotAPermutation,
+    /// A mask does not have one bit per element that the cursor spans.
+    MaskLength {
+        /// The length of the offending mask.
+        len: usize,
+        /// The expected length.
+        expected: usize,
+    },
+}
+
+impl fmt::Display for SubsetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubsetError::IndexOutOfRange { index, len } => {
+                write!(f, "index {} is out of range for {} elements", index, len)
+            }
+            SubsetError::Conflict { index } => {
+                write!(f, "index {} is both required and forbidden", index)
+            }
+            SubsetError::EmptySizeRange { min, max } => {
+                write!(f, "the size range {}..={} is empty", min, max)
+            }
+            SubsetError::TooLarge { n, max } => write!(
+                f,
+                "a dataset of {} elements exceeds the supported maximum of {}",
+                n, max
+            ),
+            SubsetError::UnsupportedOrder(order) => {
+                write!(f, "the {:?} order does not support constraints", order)
+            }
+            SubsetError::NotAPermutation => {
+                write!(f, "the order is not a permutation of the indices")
+            }
+            SubsetError::MaskLength { len, expected } => {
+                write!(
+                    f,
+                    "a mask of {} bits does not span {} elements",
+                    len, expected
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SubsetError {}
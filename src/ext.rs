@@ -0,0 +1,284 @@
+use crate::mask::Mask;
+use crate::SubsetIter;
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// Adapters for the common post-processing of subsets, which otherwise has to
+/// be written out by hand for every enumeration.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::{SubsetGenerator, SubsetIterExt};
+/// let data = vec![3, 34, 4, 12, 5, 2];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// assert!(sg.iter().sums(|&x| x as i64).any(|sum| sum == 9));
+/// ```
+pub trait SubsetIterExt<'a, T: 'a>: Iterator<Item = Vec<&'a T>> + Sized {
+    /// Pairs every subset with the indices in the dataset of its elements, in
+    /// increasing order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::{SubsetGenerator, SubsetIterExt};
+    ///
+    /// let data = vec!['a', 'b', 'c'];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.iter().with_indices();
+    ///
+    /// assert_eq!(iter.next(), Some((vec![&'a'], vec![0])));
+    /// assert_eq!(iter.next(), Some((vec![&'b'], vec![1])));
+    /// assert_eq!(iter.next(), Some((vec![&'a', &'b'], vec![0, 1])));
+    /// ```
+    fn with_indices(self) -> WithIndices<'a, T>;
+
+    /// Maps every subset to the total `weight` of its elements. The subsets
+    /// are generated into a single reused buffer, so only the totals are
+    /// produced and no subset is allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::{SubsetGenerator, SubsetIterExt};
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, true);
+    /// let sums: Vec<i64> = sg.iter().sums(|&x| x as i64).collect();
+    ///
+    /// assert_eq!(sums, vec![0, 1, 2, 3, 3, 4, 5, 6]);
+    /// ```
+    fn sums<F>(self, weight: F) -> Sums<'a, T, F>
+    where
+        F: Fn(&T) -> i64;
+
+    /// Groups the subsets into batches of `batch` subsets each, in order,
+    /// where only the last batch may be smaller. Batches suit work that has a
+    /// cost per item, such as sending subsets to a thread pool or writing them
+    /// to disk.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::{SubsetGenerator, SubsetIterExt};
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.iter().chunked(3);
+    ///
+    /// assert_eq!(iter.next(), Some(vec![vec![&1], vec![&2], vec![&1, &2]]));
+    /// assert_eq!(iter.next().map(|chunk| chunk.len()), Some(3));
+    /// assert_eq!(iter.next(), Some(vec![vec![&1, &2, &3]]));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    fn chunked(self, batch: usize) -> ChunkedSubsetIter<'a, T>;
+
+    /// Picks `k` of the subsets uniformly at random in a single pass over the
+    /// iterator, see [`reservoir_sample`](crate::reservoir_sample). Only
+    /// available with the `rand` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use subset_generator::{SubsetGenerator, SubsetIterExt};
+    ///
+    /// let data = vec![1, 2, 3, 4];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut rng = StdRng::seed_from_u64(3);
+    ///
+    /// assert_eq!(sg.iter().reservoir_sample(5, &mut rng).len(), 5);
+    /// ```
+    #[cfg(feature = "rand")]
+    fn reservoir_sample<R>(self, k: usize, rng: &mut R) -> Vec<Vec<&'a T>>
+    where
+        R: rand::Rng + ?Sized,
+    {
+        crate::reservoir_sample(self, k, rng)
+    }
+}
+
+impl<'a, T> SubsetIterExt<'a, T> for SubsetIter<'a, T> {
+    fn with_indices(self) -> WithIndices<'a, T> {
+        WithIndices { iter: self }
+    }
+
+    fn sums<F>(self, weight: F) -> Sums<'a, T, F>
+    where
+        F: Fn(&T) -> i64,
+    {
+        Sums {
+            iter: self,
+            weight,
+            buf: Vec::new(),
+        }
+    }
+
+    fn chunked(self, batch: usize) -> ChunkedSubsetIter<'a, T> {
+        assert!(batch > 0, "the batch size must be positive");
+        ChunkedSubsetIter { iter: self, batch }
+    }
+}
+
+/// Iterator over the subsets together with the indices of their elements,
+/// created by [`SubsetIterExt::with_indices`].
+pub struct WithIndices<'a, T> {
+    iter: SubsetIter<'a, T>,
+}
+
+impl<'a, T> Iterator for WithIndices<'a, T> {
+    type Item = (Vec<&'a T>, Vec<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The pending base subset is reported before the cursor moves.
+        let base = self.iter.with_emptyset;
+        let subset = self.iter.next()?;
+        let mask = if base { None } else { Some(&self.iter.set) };
+        let mut indices = Vec::with_capacity(subset.len());
+        self.iter.indices_into(mask, &mut indices);
+        Some((subset, indices))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for WithIndices<'a, T> {}
+
+impl<'a, T> FusedIterator for WithIndices<'a, T> {}
+
+/// Iterator over the total weights of the subsets, created by
+/// [`SubsetIterExt::sums`].
+pub struct Sums<'a, T, F> {
+    iter: SubsetIter<'a, T>,
+    weight: F,
+    buf: Vec<&'a T>,
+}
+
+impl<'a, T, F> Iterator for Sums<'a, T, F>
+where
+    F: Fn(&T) -> i64,
+{
+    type Item = i64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.iter.next_into(&mut self.buf) {
+            return None;
+        }
+        Some(self.buf.iter().map(|x| (self.weight)(x)).sum())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T, F> ExactSizeIterator for Sums<'a, T, F> where F: Fn(&T) -> i64 {}
+
+impl<'a, T, F> FusedIterator for Sums<'a, T, F> where F: Fn(&T) -> i64 {}
+
+/// Iterator over batches of subsets, created by [`SubsetIterExt::chunked`].
+pub struct ChunkedSubsetIter<'a, T> {
+    iter: SubsetIter<'a, T>,
+    batch: usize,
+}
+
+impl<'a, T> Iterator for ChunkedSubsetIter<'a, T> {
+    type Item = Vec<Vec<&'a T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk: Vec<_> = self.iter.by_ref().take(self.batch).collect();
+        if chunk.is_empty() {
+            None
+        } else {
+            Some(chunk)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let chunks = |n: usize| n.div_ceil(self.batch);
+        (chunks(lower), upper.map(chunks))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for ChunkedSubsetIter<'a, T> {}
+
+impl<'a, T> FusedIterator for ChunkedSubsetIter<'a, T> {}
+
+impl<'a, T> SubsetIter<'a, T> {
+    /// Appends the indices in the dataset of the elements selected by `mask`
+    /// to `buf`, or the required indices if `mask` is `None`.
+    pub(crate) fn indices_into(&self, mask: Option<&Mask>, buf: &mut Vec<usize>) {
+        match &self.constraints {
+            Some(constraints) => {
+                buf.extend((0..self.data.len()).filter(|&i| constraints.selects(i, mask)))
+            }
+            None => {
+                if let Some(mask) = mask {
+                    mask.for_each_one(|i| buf.push(i));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{SubsetGenerator, SubsetIterExt};
+
+    #[test]
+    fn indices_match_elements() {
+        let data: Vec<usize> = (0..7).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let constrained = generator.clone().with_required(&[4]).with_forbidden(&[1]);
+            for sg in &[generator, constrained] {
+                let mut count = 0;
+                for (subset, indices) in sg.iter().with_indices() {
+                    let expected: Vec<usize> = subset.into_iter().copied().collect();
+                    assert_eq!(indices, expected);
+                    count += 1;
+                }
+                assert_eq!(count, sg.iter().len());
+            }
+        }
+    }
+
+    #[test]
+    fn sums_match_subsets() {
+        let data: Vec<i32> = vec![5, -3, 8, 1, -7];
+        let generator = SubsetGenerator::new(&data, true);
+        let expected: Vec<i64> = generator
+            .iter()
+            .map(|s| s.into_iter().map(|&x| x as i64).sum())
+            .collect();
+        let sums: Vec<i64> = generator.iter().sums(|&x| x as i64).collect();
+        assert_eq!(sums, expected);
+        assert_eq!(generator.iter().sums(|&x| x as i64).len(), 32);
+    }
+
+    #[test]
+    fn chunks_concatenate() {
+        let data: Vec<usize> = (0..6).collect();
+        let generator = SubsetGenerator::new(&data, true).with_forbidden(&[2]);
+        let expected: Vec<_> = generator.iter().collect();
+        for batch in 1..40 {
+            let chunks: Vec<_> = generator.iter().chunked(batch).collect();
+            assert_eq!(chunks.len(), expected.len().div_ceil(batch));
+            assert_eq!(
+                generator.iter().chunked(batch).size_hint().1,
+                Some(chunks.len())
+            );
+            assert!(chunks.iter().all(|chunk| chunk.len() <= batch));
+            assert_eq!(chunks.concat(), expected);
+        }
+    }
+}
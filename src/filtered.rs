@@ -0,0 +1,118 @@
+use crate::{SubsetGenerator, SubsetIter};
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// Iterator over the subsets that satisfy a predicate, created by
+/// [`SubsetGenerator::filtered`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2, 3, 4];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// assert_eq!(sg.filtered(|s| s.len() == 2).count(), 6);
+/// ```
+pub struct FilteredSubsetIter<'a, T, P> {
+    iter: SubsetIter<'a, T>,
+    keep: P,
+    buf: Vec<&'a T>,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over the subsets for which `keep` returns true, in
+    /// the order of `iter`. The subsets are generated into a single reused
+    /// buffer, so only the subsets that are kept are allocated, unlike with
+    /// `iter().filter(..)`.
+    ///
+    /// Every subset is passed to `keep`, so this is correct for any predicate.
+    /// When the predicate is monotone, i.e. every superset of a rejected subset
+    /// is rejected too, [`prune_iter`](Self::prune_iter) reports the same
+    /// subsets faster by skipping the supersets of rejected subsets without
+    /// evaluating them, albeit in a different order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![3, 34, 4, 12, 5, 2];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.filtered(|s| s.iter().copied().sum::<i32>() == 9);
+    ///
+    /// assert_eq!(iter.next(), Some(vec![&4, &5]));
+    /// assert_eq!(iter.next(), Some(vec![&3, &4, &2]));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn filtered<P>(&self, keep: P) -> FilteredSubsetIter<'a, T, P>
+    where
+        P: Fn(&[&T]) -> bool,
+    {
+        FilteredSubsetIter {
+            iter: self.iter(),
+            keep,
+            buf: Vec::with_capacity(self.data.len()),
+        }
+    }
+}
+
+impl<'a, T, P> Iterator for FilteredSubsetIter<'a, T, P>
+where
+    P: Fn(&[&T]) -> bool,
+{
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.iter.next_into(&mut self.buf) {
+            if (self.keep)(&self.buf) {
+                return Some(self.buf.clone());
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<'a, T, P> FusedIterator for FilteredSubsetIter<'a, T, P> where P: Fn(&[&T]) -> bool {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Prune, SubsetGenerator};
+
+    #[test]
+    fn filtered_matches_filter() {
+        let data: Vec<i32> = vec![4, -1, 7, 3, 0, 5, -2];
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset).with_forbidden(&[4]);
+            let odd = |s: &[&i32]| s.iter().copied().sum::<i32>() % 2 != 0;
+            let expected: Vec<_> = generator.iter().filter(|s| odd(s)).collect();
+            assert_eq!(generator.filtered(odd).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn filtered_agrees_with_pruning() {
+        let data: Vec<u32> = vec![6, 2, 9, 4, 1, 7, 3, 5];
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let sum = |s: &[&u32]| s.iter().copied().sum::<u32>();
+            let mut filtered: Vec<_> = generator.filtered(|s| sum(s) <= 12).collect();
+            let mut pruned: Vec<_> = generator
+                .prune_iter(|s| {
+                    if sum(s) <= 12 {
+                        Prune::Keep
+                    } else {
+                        Prune::RejectSupersets
+                    }
+                })
+                .collect();
+            filtered.sort();
+            pruned.sort();
+            assert_eq!(filtered, pruned);
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use crate::constraints::FreeElements;
+use crate::SubsetGenerator;
+use alloc::vec::Vec;
+use bit_vec::BitVec;
+use core::iter::FusedIterator;
+
+/// A single change between two consecutive subsets in Gray-code order, holding
+/// the index of the element in the dataset that was toggled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Toggle {
+    /// The element was not in the previous subset, and is in the current one.
+    Added(usize),
+    /// The element was in the previous subset, and is not in the current one.
+    Removed(usize),
+}
+
+/// Iterator over all the subsets in reflected binary Gray-code order, created
+/// by [`SubsetGenerator::gray_iter`]. Consecutive subsets differ in exactly one
+/// element.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, true);
+///
+/// assert_eq!(sg.gray_iter().count(), 8);
+/// ```
+pub struct GraySubsetIter<'a, T> {
+    elements: FreeElements<'a, T>,
+    set: BitVec,
+    counter: BitVec,
+    last: Option<Toggle>,
+    with_emptyset: bool,
+    exhausted: bool,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over all the subsets in reflected binary Gray-code
+    /// order: the `i`-th subset has the bitmask `i ^ (i >> 1)`. Each subset
+    /// differs from the previous one by a single element, which is available
+    /// through [`GraySubsetIter::last_toggle`]. The enumeration starts at the
+    /// empty set, which is reported if (and only if) the generator was
+    /// configured to include the empty set. The constraints of the generator
+    /// are respected: the Gray code runs over the free elements, in the order
+    /// set by [`with_order`](SubsetGenerator::with_order), and the required
+    /// elements are in every subset, so they are never toggled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::{SubsetGenerator, Toggle};
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.gray_iter();
+    ///
+    /// assert_eq!(iter.next(), Some(vec![&1]));
+    /// assert_eq!(iter.next(), Some(vec![&1, &2]));
+    /// assert_eq!(iter.last_toggle(), Some(Toggle::Added(1)));
+    /// assert_eq!(iter.next(), Some(vec![&2]));
+    /// assert_eq!(iter.last_toggle(), Some(Toggle::Removed(0)));
+    /// ```
+    pub fn gray_iter(&self) -> GraySubsetIter<'a, T> {
+        let elements = self.free_elements();
+        let len = elements.len();
+        GraySubsetIter {
+            elements,
+            set: BitVec::from_elem(len, false),
+            counter: BitVec::from_elem(len, false),
+            last: None,
+            with_emptyset: self.includes_base(),
+            exhausted: false,
+        }
+    }
+
+    /// Searches all the subsets for the best value of an objective that is
+    /// maintained incrementally, and returns the best value together with the
+    /// bitmask of its subset. The subsets are visited in Gray-code order, as
+    /// by `gray_iter`, so every step adds or removes a single element, and the
+    /// objective is updated by calling `add` or `remove` with that element.
+    /// For objectives with *O(1)* updates, such as sums, each step takes
+    /// amortized *O(1)* time instead of the *O(n)* needed to evaluate a subset
+    /// from scratch.
+    ///
+    /// `init` is the objective of the empty set, to which the required
+    /// elements are added first, and that subset is only a candidate if the
+    /// generator includes it, as for `gray_iter`. `better(a, b)` returns true
+    /// if `a` is strictly better than `b`, so of equally good subsets the
+    /// first one visited is returned. The returned mask runs over the whole
+    /// dataset. If no subset is visited at all, the objective of the required
+    /// elements and their mask are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// // The subset with the largest sum of at most 10.
+    /// let data = vec![6, 3, 8, 2];
+    /// let sg = SubsetGenerator::new(&data, true);
+    /// let (sum, mask) = sg.optimize_incremental(
+    ///     0,
+    ///     |sum, &x| *sum += x,
+    ///     |sum, &x| *sum -= x,
+    ///     |a, b| *a <= 10 && (*b > 10 || a > b),
+    /// );
+    ///
+    /// assert_eq!(sum, 10);
+    /// assert_eq!(mask.iter().collect::<Vec<_>>(), vec![false, false, true, true]);
+    /// ```
+    pub fn optimize_incremental<O, A, R, B>(
+        &self,
+        init: O,
+        mut add: A,
+        mut remove: R,
+        better: B,
+    ) -> (O, BitVec)
+    where
+        O: Clone,
+        A: FnMut(&mut O, &T),
+        R: FnMut(&mut O, &T),
+        B: Fn(&O, &O) -> bool,
+    {
+        let mut iter = self.gray_iter();
+        let mut objective = init;
+        for x in iter.elements.collect(None) {
+            add(&mut objective, x);
+        }
+        let mut best = if self.includes_base() {
+            Some((objective.clone(), iter.set.clone()))
+        } else {
+            None
+        };
+
+        while let Some(toggle) = iter.next_toggle() {
+            match toggle {
+                Toggle::Added(i) => add(&mut objective, &self.data[i]),
+                Toggle::Removed(i) => remove(&mut objective, &self.data[i]),
+            }
+            let improved = match &best {
+                Some((value, _)) => better(&objective, value),
+                None => true,
+            };
+            if improved {
+                best = Some((objective.clone(), iter.set.clone()));
+            }
+        }
+
+        let (objective, set) = best.unwrap_or((objective, iter.set));
+        (objective, iter.elements.dataset_mask(&set))
+    }
+}
+
+impl<'a, T> GraySubsetIter<'a, T> {
+    /// Advances to the next subset without collecting its elements, and returns
+    /// the element that was toggled to get there. This takes amortized *O(1)*
+    /// time, so callers maintaining an incremental solution can skip the *O(n)*
+    /// cost of building each subset. Returns `None` once all subsets have been
+    /// exhausted. A pending empty set is skipped, since it precedes the first
+    /// toggle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::{SubsetGenerator, Toggle};
+    ///
+    /// let data = vec![5, 7];
+    /// let sg = SubsetGenerator::new(&data, true);
+    /// let mut iter = sg.gray_iter();
+    /// let mut sum = 0;
+    /// while let Some(toggle) = iter.next_toggle() {
+    ///     match toggle {
+    ///         Toggle::Added(i) => sum += data[i],
+    ///         Toggle::Removed(i) => sum -= data[i],
+    ///     }
+    /// }
+    /// assert_eq!(sum, 7);
+    /// ```
+    pub fn next_toggle(&mut self) -> Option<Toggle> {
+        self.with_emptyset = false;
+        if self.exhausted {
+            return None;
+        }
+
+        // The element to toggle is the position of the lowest clear bit of the
+        // counter, which is exactly where the carry stops when adding 1.
+        for i in 0..self.counter.len() {
+            if self.counter[i] {
+                self.counter.set(i, false);
+            } else {
+                self.counter.set(i, true);
+                let index = self.elements.index(i);
+                let toggle = if self.set[i] {
+                    Toggle::Removed(index)
+                } else {
+                    Toggle::Added(index)
+                };
+                self.set.set(i, !self.set[i]);
+                self.last = Some(toggle);
+                return Some(toggle);
+            }
+        }
+
+        self.exhausted = true;
+        None
+    }
+
+    /// Returns the element that was toggled to reach the most recently reported
+    /// subset, or `None` if no element has been toggled yet.
+    pub fn last_toggle(&self) -> Option<Toggle> {
+        self.last
+    }
+}
+
+impl<'a, T> Iterator for GraySubsetIter<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.with_emptyset {
+            self.with_emptyset = false;
+            return Some(self.elements.collect(None));
+        }
+
+        self.next_toggle()?;
+        Some(self.elements.subset(&self.set))
+    }
+}
+
+impl<'a, T> FusedIterator for GraySubsetIter<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{SubsetGenerator, Toggle};
+    use std::collections::HashSet;
+
+    #[test]
+    fn gray_single_change() {
+        let data: Vec<usize> = (0..8).collect();
+        let generator = SubsetGenerator::new(&data, true);
+        let mut iter = generator.gray_iter();
+        let mut previous: HashSet<usize> = HashSet::new();
+        let mut seen = HashSet::new();
+
+        assert_eq!(iter.next(), Some(vec![]));
+        seen.insert(vec![]);
+        while let Some(subset) = iter.next() {
+            let current: HashSet<usize> = subset.iter().map(|&&x| x).collect();
+            let difference: Vec<_> = current.symmetric_difference(&previous).collect();
+            assert_eq!(difference.len(), 1);
+
+            let index = *difference[0];
+            match iter.last_toggle() {
+                Some(Toggle::Added(i)) => assert!(i == index && current.contains(&i)),
+                Some(Toggle::Removed(i)) => assert!(i == index && !current.contains(&i)),
+                None => panic!("missing toggle"),
+            }
+
+            seen.insert(subset.into_iter().copied().collect::<Vec<_>>());
+            previous = current;
+        }
+        assert_eq!(seen.len(), 256);
+    }
+
+    #[test]
+    fn gray_without_empty() {
+        let data: Vec<usize> = (0..5).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        assert_eq!(generator.gray_iter().count(), 31);
+        assert!(generator.gray_iter().all(|s| !s.is_empty()));
+    }
+
+    #[test]
+    fn gray_toggles() {
+        let data: Vec<usize> = (0..4).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        let mut iter = generator.gray_iter();
+        let mut toggles = 0;
+        while iter.next_toggle().is_some() {
+            toggles += 1;
+        }
+        assert_eq!(toggles, 15);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn incremental_matches_brute_force() {
+        let data: Vec<i64> = vec![7, -3, 12, 5, -9, 4, 1, -6];
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let score = |sum: i64| -(sum - 9).abs();
+            let (sum, mask) = generator.optimize_incremental(
+                0,
+                |sum, &x| *sum += x,
+                |sum, &x| *sum -= x,
+                |a, b| score(*a) > score(*b),
+            );
+            let best = generator
+                .iter()
+                .map(|s| s.into_iter().sum::<i64>())
+                .max_by_key(|&sum| score(sum))
+                .unwrap();
+            assert_eq!(score(sum), score(best));
+            let selected: i64 = (0..data.len()).filter(|&i| mask[i]).map(|i| data[i]).sum();
+            assert_eq!(selected, sum);
+        }
+
+        let empty: Vec<i64> = vec![];
+        let (sum, mask) = SubsetGenerator::new(&empty, false).optimize_incremental(
+            0,
+            |sum, &x| *sum += x,
+            |sum, &x| *sum -= x,
+            |a, b| a > b,
+        );
+        assert_eq!((sum, mask.len()), (0, 0));
+    }
+}
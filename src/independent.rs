@@ -0,0 +1,229 @@
+use crate::constraints::FreeElements;
+use crate::SubsetGenerator;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// Iterator over the subsets that contain no conflicting pair of elements,
+/// created by [`SubsetGenerator::independent_iter`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// // {1, 2} and {1, 2, 3} contain the conflict.
+/// assert_eq!(sg.independent_iter(&[(0, 1)]).count(), 5);
+/// ```
+pub struct IndependentSubsetIter<'a, T> {
+    elements: FreeElements<'a, T>,
+    neighbors: Vec<Vec<usize>>,
+    blocked: Vec<usize>,
+    chosen: Vec<usize>,
+    with_emptyset: bool,
+    started: bool,
+    exhausted: bool,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over the subsets that do not contain both elements
+    /// of any of the `conflicts`, which are pairs of indices into the dataset.
+    /// Viewing the conflicts as the edges of a graph on the elements, these are
+    /// exactly its independent sets. A conflict of an element with itself
+    /// excludes that element from all subsets.
+    ///
+    /// The subsets are visited depth-first, in lexicographic order of the
+    /// included indices like [`prune_iter`](Self::prune_iter). As every
+    /// superset of a subset with a conflict has that conflict too, such
+    /// subsets are never generated: an element is only added if it conflicts
+    /// with none of the chosen ones, which is tracked per element, so each
+    /// step only costs the degree of the elements that are added or removed.
+    ///
+    /// Like `prune_iter`, this respects the constraints of the generator. A
+    /// free element that conflicts with a required one is never added,
+    /// conflicts with forbidden elements are ignored, and if two required
+    /// elements conflict, there are no subsets at all.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index of a conflict is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// // The path a - b - c.
+    /// let data = vec!['a', 'b', 'c'];
+    /// let sg = SubsetGenerator::new(&data, true);
+    /// let independent: Vec<_> = sg.independent_iter(&[(0, 1), (1, 2)]).collect();
+    ///
+    /// assert_eq!(
+    ///     independent,
+    ///     vec![vec![], vec![&'a'], vec![&'a', &'c'], vec![&'b'], vec![&'c']]
+    /// );
+    /// ```
+    pub fn independent_iter(&self, conflicts: &[(usize, usize)]) -> IndependentSubsetIter<'a, T> {
+        let elements = self.free_elements();
+        let len = elements.len();
+        let mut neighbors = vec![Vec::new(); len];
+        let mut blocked = vec![0; len];
+        let mut infeasible = false;
+        for &(u, v) in conflicts {
+            let index = u.max(v);
+            assert!(
+                index < self.data.len(),
+                "index {} is out of range for {} elements",
+                index,
+                self.data.len()
+            );
+            let is_required = |i: usize| elements.required().binary_search(&i).is_ok();
+            // The conflict runs between the bits of free elements, unless an
+            // element is required or forbidden.
+            match (elements.bit(u), elements.bit(v)) {
+                // The element blocks itself permanently.
+                (Some(u), Some(v)) if u == v => blocked[u] += 1,
+                (Some(u), Some(v)) => {
+                    neighbors[u].push(v);
+                    neighbors[v].push(u);
+                }
+                (Some(j), None) | (None, Some(j)) => {
+                    if is_required(u) || is_required(v) {
+                        blocked[j] += 1;
+                    }
+                }
+                (None, None) => infeasible |= is_required(u) && is_required(v),
+            }
+        }
+        IndependentSubsetIter {
+            elements,
+            neighbors,
+            blocked,
+            chosen: Vec::with_capacity(len),
+            with_emptyset: self.includes_base() && !infeasible,
+            started: false,
+            exhausted: infeasible,
+        }
+    }
+}
+
+impl<'a, T> IndependentSubsetIter<'a, T> {
+    /// Returns the first element from `start` on that conflicts with none of
+    /// the chosen elements.
+    fn first_free(&self, start: usize) -> Option<usize> {
+        (start..self.elements.len()).find(|&j| self.blocked[j] == 0)
+    }
+
+    /// Adds the element at index `j` to the subset.
+    fn push(&mut self, j: usize) {
+        self.chosen.push(j);
+        for &k in &self.neighbors[j] {
+            self.blocked[k] += 1;
+        }
+    }
+
+    /// Removes the last element from the subset, and returns its index.
+    fn pop(&mut self) -> Option<usize> {
+        let j = self.chosen.pop()?;
+        for &k in &self.neighbors[j] {
+            self.blocked[k] -= 1;
+        }
+        Some(j)
+    }
+
+    /// Moves to the next independent set in depth-first order: the first
+    /// extension of the current one, or otherwise the next sibling of the
+    /// current set or of its closest ancestor that has one. Returns false once
+    /// the search is exhausted.
+    fn advance(&mut self) -> bool {
+        let start = self.chosen.last().map_or(0, |&last| last + 1);
+        let mut next = self.first_free(start);
+        while next.is_none() {
+            match self.pop() {
+                Some(last) => next = self.first_free(last + 1),
+                None => return false,
+            }
+        }
+        self.push(next.unwrap());
+        true
+    }
+}
+
+impl<'a, T> Iterator for IndependentSubsetIter<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            if self.with_emptyset {
+                return Some(self.elements.collect(None));
+            }
+        }
+        if self.exhausted || !self.advance() {
+            self.exhausted = true;
+            return None;
+        }
+        Some(self.elements.collect(self.chosen.iter().copied()))
+    }
+}
+
+impl<'a, T> FusedIterator for IndependentSubsetIter<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+
+    #[test]
+    fn independent_matches_filter() {
+        let data: Vec<usize> = (0..8).collect();
+        // A cycle on 0..6 with a chord, a pendant vertex, and a self-loop.
+        let conflicts = [
+            (0, 1),
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 0),
+            (1, 4),
+            (6, 2),
+            (7, 7),
+        ];
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let independent: Vec<_> = generator.independent_iter(&conflicts).collect();
+            let mut expected: Vec<_> = generator
+                .iter()
+                .filter(|s| {
+                    conflicts
+                        .iter()
+                        .all(|(u, v)| !(s.contains(&&data[*u]) && s.contains(&&data[*v])))
+                })
+                .collect();
+            // Lexicographic order is the sorted order of the index lists.
+            expected.sort();
+            assert_eq!(independent, expected);
+        }
+    }
+
+    #[test]
+    fn independent_without_conflicts() {
+        let data: Vec<usize> = (0..5).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        let mut iter = generator.independent_iter(&[]);
+        assert_eq!(iter.by_ref().count(), 31);
+        assert_eq!(iter.next(), None);
+
+        let empty: Vec<usize> = vec![];
+        let generator = SubsetGenerator::new(&empty, true);
+        assert_eq!(generator.independent_iter(&[]).count(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn independent_out_of_range() {
+        let data = vec![1, 2, 3];
+        SubsetGenerator::new(&data, false).independent_iter(&[(0, 3)]);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::{SubsetGenerator, SubsetIter};
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// Iterator over the indices of the elements of every subset, created by
+/// [`SubsetGenerator::index_iter`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec!["a", "b"];
+/// let sg = SubsetGenerator::new(&data, true);
+///
+/// let indices: Vec<Vec<usize>> = sg.index_iter().collect();
+/// assert_eq!(indices, vec![vec![], vec![0], vec![1], vec![0, 1]]);
+/// ```
+pub struct IndexSubsetIter<'a, T> {
+    iter: SubsetIter<'a, T>,
+}
+
+/// Iterator over the subsets together with their indices, created by
+/// [`SubsetGenerator::indexed_iter`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec!["a", "b"];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// let indices: Vec<u64> = sg.indexed_iter().map(|(index, _)| index).collect();
+/// assert_eq!(indices, vec![1, 2, 3]);
+/// ```
+pub struct IndexedSubsetIter<'a, T> {
+    iter: SubsetIter<'a, T>,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over all the subsets, where each subset is given by
+    /// the indices in the dataset of its elements, in increasing order. No
+    /// references to the elements are created, so the selections can be
+    /// stored independently of the dataset. The subsets are reported in the
+    /// same order as by `iter`, starting with an empty vector if the generator
+    /// includes the empty set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![String::from("x"), String::from("y"), String::from("z")];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.index_iter();
+    ///
+    /// assert_eq!(iter.next(), Some(vec![0]));
+    /// assert_eq!(iter.next(), Some(vec![1]));
+    /// assert_eq!(iter.next(), Some(vec![0, 1]));
+    /// ```
+    pub fn index_iter(&self) -> IndexSubsetIter<'a, T> {
+        IndexSubsetIter { iter: self.iter() }
+    }
+
+    /// Returns an iterator over all the subsets, each paired with its index,
+    /// in the same order as `iter`. Unlike the counter of
+    /// [`enumerate`](Iterator::enumerate), the index is the one used by
+    /// [`SubsetIter::seek`] and [`current_index`](SubsetIter::current_index):
+    /// the integer value of the bitmask of the subset, so the empty set has
+    /// index 0 whether it is reported or not. With required or forbidden
+    /// elements, the bitmask only covers the free elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics while iterating if an index does not fit in a `u64`, which can
+    /// only happen for datasets of more than 64 elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.indexed_iter();
+    ///
+    /// assert_eq!(iter.next(), Some((1, vec![&1])));
+    /// assert_eq!(iter.next(), Some((2, vec![&2])));
+    /// assert_eq!(iter.nth(2), Some((5, vec![&1, &3])));
+    /// assert_eq!(sg.iter().seek(5), Some(vec![&1, &3]));
+    /// ```
+    pub fn indexed_iter(&self) -> IndexedSubsetIter<'a, T> {
+        IndexedSubsetIter { iter: self.iter() }
+    }
+}
+
+impl<'a, T> Iterator for IndexSubsetIter<'a, T> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut indices = Vec::new();
+        if self.iter.with_emptyset {
+            self.iter.with_emptyset = false;
+            self.iter.indices_into(None, &mut indices);
+        } else if self.iter.next_set() {
+            self.iter.indices_into(Some(&self.iter.set), &mut indices);
+        } else {
+            return None;
+        }
+        Some(indices)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IndexSubsetIter<'a, T> {}
+
+impl<'a, T> FusedIterator for IndexSubsetIter<'a, T> {}
+
+impl<'a, T> Iterator for IndexedSubsetIter<'a, T> {
+    type Item = (u64, Vec<&'a T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The pending base subset is reported before the cursor moves.
+        let base = self.iter.with_emptyset;
+        let subset = self.iter.next()?;
+        let index = if base { 0 } else { self.iter.current_index() };
+        Some((index, subset))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IndexedSubsetIter<'a, T> {}
+
+impl<'a, T> FusedIterator for IndexedSubsetIter<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+
+    #[test]
+    fn indices_match_references() {
+        let data: Vec<usize> = (0..6).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let constrained = generator.clone().with_required(&[2]).with_forbidden(&[5]);
+            for sg in &[generator, constrained] {
+                let expected: Vec<Vec<usize>> = sg
+                    .iter()
+                    .map(|s| s.into_iter().copied().collect())
+                    .collect();
+                let indices: Vec<Vec<usize>> = sg.index_iter().collect();
+                assert_eq!(indices, expected);
+                assert_eq!(sg.index_iter().len(), expected.len());
+            }
+        }
+    }
+
+    #[test]
+    fn indexed_iter_seeks_back() {
+        let data: Vec<usize> = (0..6).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let constrained = generator.clone().with_required(&[1]).with_forbidden(&[4]);
+            for sg in &[generator, constrained] {
+                let mut count = 0;
+                let mut previous = None;
+                for (index, subset) in sg.indexed_iter() {
+                    assert!(previous < Some(index));
+                    previous = Some(index);
+                    let mut iter = sg.iter();
+                    assert_eq!(iter.seek(index), Some(subset));
+                    count += 1;
+                }
+                assert_eq!(count, sg.iter().len());
+                assert_eq!(sg.indexed_iter().len(), count);
+            }
+        }
+    }
+}
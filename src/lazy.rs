@@ -0,0 +1,206 @@
+use crate::mask::Mask;
+use crate::{SubsetGenerator, SubsetIter};
+use bit_vec::BitVec;
+use core::iter::FusedIterator;
+
+/// Iterator over all the subsets, where each subset is itself a lazy iterator
+/// over its elements, created by [`SubsetGenerator::lazy_iter`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// let sizes: Vec<usize> = sg.lazy_iter().map(|s| s.len()).collect();
+/// assert_eq!(sizes, vec![1, 1, 2, 1, 2, 2, 3]);
+/// ```
+pub struct LazySubsetIter<'a, T> {
+    iter: SubsetIter<'a, T>,
+}
+
+/// Iterator over the elements of a single subset, reported by
+/// [`LazySubsetIter`]. The elements are found by walking the bits of the mask
+/// of the subset on demand, in the order of the dataset.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// let last = sg.lazy_iter().last().unwrap();
+/// assert_eq!(last.copied().max(), Some(3));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SubsetElements<'a, T> {
+    data: &'a [T],
+    mask: Mask,
+    pos: usize,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over all the subsets, in the order of `iter`, where
+    /// each subset is an iterator over references to its elements instead of a
+    /// vector. The elements are only located when the inner iterator is
+    /// advanced, so consumers that fold over the elements of every subset,
+    /// such as summing them, never allocate: for datasets of at most 64
+    /// elements, the inner iterator holds the mask of the subset as a `u64`.
+    /// Larger datasets copy the mask into a BitVec for every subset, which is
+    /// still cheaper than a vector of references.
+    ///
+    /// The inner iterators own their masks, so they remain valid after the
+    /// outer iterator has moved on. The constraints of the generator are
+    /// respected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![3, 34, 4, 12, 5, 2];
+    /// let sg = SubsetGenerator::new(&data, false);
+    ///
+    /// let nines = sg.lazy_iter().filter(|s| s.clone().sum::<i32>() == 9);
+    /// assert_eq!(nines.count(), 2);
+    /// ```
+    pub fn lazy_iter(&self) -> LazySubsetIter<'a, T> {
+        LazySubsetIter { iter: self.iter() }
+    }
+}
+
+impl<'a, T> SubsetIter<'a, T> {
+    /// Returns the mask over the whole dataset of the subset selected by
+    /// `mask`, or of the base subset if `mask` is `None`, which also covers
+    /// the required elements.
+    fn dataset_mask(&self, mask: Option<&Mask>) -> Mask {
+        let len = self.data.len();
+        match (&self.constraints, mask) {
+            (None, Some(mask)) => mask.clone(),
+            (None, None) => Mask::from_index(0, len),
+            (Some(constraints), mask) if len <= 64 => Mask::Small {
+                bits: (0..len)
+                    .filter(|&i| constraints.selects(i, mask))
+                    .fold(0, |bits, i| bits | 1 << i),
+                len,
+            },
+            (Some(constraints), mask) => {
+                Mask::Large(BitVec::from_fn(len, |i| constraints.selects(i, mask)))
+            }
+        }
+    }
+}
+
+impl<'a, T> Iterator for LazySubsetIter<'a, T> {
+    type Item = SubsetElements<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let iter = &mut self.iter;
+        let mask = if iter.with_emptyset {
+            iter.with_emptyset = false;
+            iter.dataset_mask(None)
+        } else if iter.next_set() {
+            iter.dataset_mask(Some(&iter.set))
+        } else {
+            return None;
+        };
+        Some(SubsetElements {
+            data: iter.data,
+            mask,
+            pos: 0,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for LazySubsetIter<'a, T> {}
+
+impl<'a, T> FusedIterator for LazySubsetIter<'a, T> {}
+
+impl<'a, T> Iterator for SubsetElements<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.mask {
+            Mask::Small { bits, .. } => {
+                if *bits == 0 {
+                    return None;
+                }
+                let i = bits.trailing_zeros() as usize;
+                *bits &= *bits - 1;
+                Some(&self.data[i])
+            }
+            Mask::Large(set) => {
+                while self.pos < set.len() {
+                    let i = self.pos;
+                    self.pos += 1;
+                    if set[i] {
+                        return Some(&self.data[i]);
+                    }
+                }
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = match &self.mask {
+            Mask::Small { bits, .. } => bits.count_ones() as usize,
+            Mask::Large(set) => (self.pos..set.len()).filter(|&i| set[i]).count(),
+        };
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for SubsetElements<'a, T> {}
+
+impl<'a, T> FusedIterator for SubsetElements<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+
+    #[test]
+    fn lazy_matches_iter() {
+        for &n in &[0, 6, 70] {
+            let data: Vec<usize> = (0..n).collect();
+            for &with_emptyset in &[false, true] {
+                let generator = SubsetGenerator::new(&data, with_emptyset);
+                let required = if n > 0 { vec![n / 2] } else { vec![] };
+                let constrained = generator
+                    .clone()
+                    .with_required(&required)
+                    .with_forbidden(&required.iter().map(|&i| i / 2).collect::<Vec<_>>());
+                for sg in &[generator, constrained] {
+                    let lazy = sg.lazy_iter();
+                    assert_eq!(lazy.size_hint(), sg.iter().size_hint());
+                    for (elements, subset) in lazy.zip(sg.iter()).take(200) {
+                        assert_eq!(elements.len(), subset.len());
+                        assert_eq!(elements.collect::<Vec<_>>(), subset);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lazy_elements_outlive_step() {
+        let data = vec![1, 2, 3];
+        let generator = SubsetGenerator::new(&data, true)
+            .with_order(&[2, 0, 1])
+            .unwrap();
+        let mut iter = generator.lazy_iter();
+        let first = iter.next().unwrap();
+        let second = iter.next().unwrap();
+        let rest: Vec<Vec<&i32>> = iter.map(|s| s.collect()).collect();
+        assert_eq!(first.count(), 0);
+        assert_eq!(second.collect::<Vec<_>>(), vec![&3]);
+        let expected: Vec<_> = generator.iter().skip(2).collect();
+        assert_eq!(rest, expected);
+    }
+}
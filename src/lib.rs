@@ -6,12 +6,139 @@
 //! ensures that the memory usage is *O(n)* at any point when using the
 //! generator. The overall complexity is *O(n)* per item, and, of course,
 //! *O(n * 2^n)* to generate all items.
+//!
+//! The crate only needs an allocator, so it supports `no_std` targets when the
+//! default `std` feature is disabled. The `rayon` feature requires `std`.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
 use bit_vec::BitVec;
+use constraints::Constraints;
+use core::fmt;
+use core::iter::FusedIterator;
+use mask::Mask;
+
+mod at_least;
+#[cfg(feature = "big")]
+mod big;
+mod bipartition;
+mod bounded;
+mod builder;
+mod cardinality;
+mod cloned;
+mod colex;
+mod combinations;
+mod complement;
+mod compress;
+mod constraints;
+#[cfg(feature = "std")]
+mod dedup;
+mod delta;
+mod error;
+mod ext;
+mod filtered;
+mod gray;
+mod independent;
+mod indices;
+mod lazy;
+mod lex;
+mod mask;
+mod mask_generator;
+mod masked;
+mod maximal;
+mod meet;
+mod multiset;
+mod owned;
+#[cfg(feature = "rayon")]
+mod par;
+mod popcount;
+mod predicate;
+mod product;
+mod project;
+mod prune;
+pub mod rank;
+#[cfg(feature = "rand")]
+mod sample;
+mod shuffled;
+mod split;
+mod state;
+mod streaming;
+mod submask;
+mod subset_sum;
+mod swap;
+mod weight;
+
+pub use at_least::AtLeastIter;
+pub use bipartition::BipartitionIter;
+pub use bounded::BoundedSubsetIter;
+pub use builder::{EmptysetPosition, Order, SubsetGeneratorBuilder, Subsets};
+pub use cardinality::CardinalityGroupedIter;
+pub use cloned::{ClonedSubsetIter, OwnedClonedIter};
+pub use colex::ColexSubsetIter;
+pub use combinations::KSubsetIter;
+pub use complement::ComplementIter;
+#[cfg(feature = "std")]
+pub use dedup::DedupSubsetIter;
+pub use delta::SubsetDelta;
+pub use error::SubsetError;
+pub use ext::{ChunkedSubsetIter, SubsetIterExt, Sums, WithIndices};
+pub use filtered::FilteredSubsetIter;
+pub use gray::{GraySubsetIter, Toggle};
+pub use independent::IndependentSubsetIter;
+pub use indices::{IndexSubsetIter, IndexedSubsetIter};
+pub use lazy::{LazySubsetIter, SubsetElements};
+pub use lex::LexSubsetIter;
+pub use mask_generator::{MaskGenerator, MaskIndices};
+pub use masked::{BitVecSubsetIter, MaskedSubsetIter};
+pub use maximal::MaximalSubsetIter;
+pub use multiset::MultisetIter;
+pub use owned::{OwnedSubsetGenerator, SetSubsetGenerator, SetSubsetIter};
+#[cfg(feature = "rayon")]
+pub use par::ParSubsetIter;
+pub use popcount::PopcountOrderedIter;
+pub use product::{product_subsets, ProductSubsetIter};
+pub use project::ProjectedSubsetIter;
+pub use prune::{Prune, PruneSubsetIter};
+#[cfg(feature = "rand")]
+pub use sample::reservoir_sample;
+pub use shuffled::ShuffledSubsetIter;
+pub use split::LayerSplitIter;
+pub use state::SubsetState;
+pub use streaming::StreamingSubsetIter;
+pub use submask::SubmaskIter;
+pub use swap::{swap_iter, SwapIter};
+pub use weight::WeightOrderedIter;
+
+#[derive(Debug)]
 pub struct SubsetGenerator<'a, T> {
-    data: &'a Vec<T>,
+    data: &'a [T],
     with_emptyset: bool,
+    constraints: Option<Constraints>,
+    size: Option<(usize, usize)>,
+    order: Order,
+    emptyset_last: bool,
+    report_interval: u64,
+}
+
+/// The number of subsets between two reports of [`SubsetGenerator::search`],
+/// unless configured otherwise with the builder.
+const DEFAULT_REPORT_INTERVAL: u64 = 1 << 20;
+
+/// Rejects arrays of more than 64 elements in
+/// [`SubsetGenerator::from_array`] at compile time, as evaluating `FITS` for
+/// such an `N` fails.
+struct ArrayLen<const N: usize>;
+
+impl<const N: usize> ArrayLen<N> {
+    const FITS: () = assert!(N <= 64, "from_array supports arrays of at most 64 elements");
 }
 
+/// Iterator over all the subsets, created by [`SubsetGenerator::iter`]. It only
+/// borrows the dataset and otherwise owns its state, so it is `Send` and `Sync`
+/// whenever `T: Sync`. The iterators returned by `split` can therefore be moved
+/// into the threads of a `std::thread::scope` without needing rayon.
 ///
 /// # Examples
 ///
@@ -24,15 +151,34 @@ pub struct SubsetGenerator<'a, T> {
 /// assert_eq!(sg.iter().count(), 3);
 /// ```
 pub struct SubsetIter<'a, T> {
-    data: &'a Vec<T>,
-    set: BitVec,
+    data: &'a [T],
+    set: Mask,
+    back: Mask,
     with_emptyset: bool,
+    emptyset: bool,
+    constraints: Option<Constraints>,
+    // Set once the front cursor has met the back cursor, so that calls past
+    // the end return at once instead of comparing the cursors again.
+    exhausted: bool,
+    // Set while the subset at the front cursor is the last one reported, by
+    // stepping forward from its predecessor, so that `next_delta` may report
+    // the change from it. Moving the cursor in any other way clears it.
+    reported: bool,
 }
 
 impl<'a, T> SubsetGenerator<'a, T> {
-    /// Constructs a new container holding the (linearized) data set. If
-    /// `with_emptyset` is true, then the generator will also output the empty
-    /// vector. Otherwise, only subsets with at least one element are reported.
+    /// Constructs a new container holding the (linearized) data set. The data
+    /// can be any slice-like container, such as a `Vec`, an array, or a slice.
+    /// If `with_emptyset` is true, then the generator will also output the
+    /// empty vector. Otherwise, only subsets with at least one element are
+    /// reported. In particular, the only subset of an empty dataset is the
+    /// empty set, so the generator then yields exactly one empty vector if
+    /// `with_emptyset` is true, and nothing otherwise.
+    ///
+    /// Any number of elements is accepted, but the index-based operations
+    /// (such as `seek`, `masked_iter`, and `split`) use `u64` indices, which
+    /// only describe the subsets of at most 64 elements. Use
+    /// [`try_new`](Self::try_new) to reject larger datasets up front.
     ///
     /// Examples
     /// ```
@@ -40,39 +186,112 @@ impl<'a, T> SubsetGenerator<'a, T> {
     ///
     /// let data = vec![1, 2, 3, 4];
     /// let sg = SubsetGenerator::new(&data, false);
+    ///
+    /// let sg = SubsetGenerator::new(&[1, 2, 3, 4], false);
+    /// let sg = SubsetGenerator::new(&data[1..], false);
     /// ```
-    pub fn new(data: &Vec<T>, with_emptyset: bool) -> SubsetGenerator<T> {
+    pub fn new<D>(data: &'a D, with_emptyset: bool) -> SubsetGenerator<'a, T>
+    where
+        D: AsRef<[T]> + ?Sized,
+    {
         SubsetGenerator {
-            data,
+            data: data.as_ref(),
             with_emptyset,
+            constraints: None,
+            size: None,
+            order: Order::Binary,
+            emptyset_last: false,
+            report_interval: DEFAULT_REPORT_INTERVAL,
         }
     }
 
-    /// Consumes self and returns an iterator over all the subsets. The
-    /// underlying dataset cannot be consumed, so references are still returned.
-    /// The generator returns an emptyset if (and only if) the generator was
-    /// configured to include the empty set. In case the underlying dataset does
-    /// not mutate, consider using the `iter` version instead.
+    /// Constructs a generator like [`new`](Self::new), but returns an error if
+    /// the dataset has more elements than the `u64` subset indices support,
+    /// which is 64 elements. With the `big` feature, the indices are also
+    /// available as `BigUint`, so there is no limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SubsetError::TooLarge`] if the dataset is too large.
     ///
     /// # Examples
     ///
     /// ```
-    /// use subset_generator::SubsetGenerator;
+    /// use subset_generator::{SubsetError, SubsetGenerator};
     ///
-    /// let mut data = vec![1, 2, 3];
-    /// let sg = SubsetGenerator::new(&data, true);
-    /// assert_eq!(sg.into_iter().count(), 8);
+    /// let data = vec![0; 10];
+    /// assert!(SubsetGenerator::try_new(&data, false).is_ok());
     ///
-    /// data = vec![42];
-    /// // additional computations...
+    /// # if !cfg!(feature = "big") {
+    /// let data = vec![0; 65];
+    /// let result = SubsetGenerator::try_new(&data, false);
+    /// assert_eq!(result.unwrap_err(), SubsetError::TooLarge { n: 65, max: 64 });
+    /// # }
     /// ```
-    pub fn into_iter(self) -> SubsetIter<'a, T> {
-        let len = self.data.len();
-        SubsetIter {
-            data: &self.data,
-            set: BitVec::from_elem(len, false),
-            with_emptyset: self.with_emptyset,
+    pub fn try_new<D>(
+        data: &'a D,
+        with_emptyset: bool,
+    ) -> Result<SubsetGenerator<'a, T>, SubsetError>
+    where
+        D: AsRef<[T]> + ?Sized,
+    {
+        #[cfg(not(feature = "big"))]
+        {
+            let n = data.as_ref().len();
+            if n > 64 {
+                return Err(SubsetError::TooLarge { n, max: 64 });
+            }
         }
+        Ok(SubsetGenerator::new(data, with_emptyset))
+    }
+
+    /// Constructs a generator over a fixed-size array, like
+    /// [`new`](Self::new), but with the size checked at compile time: arrays
+    /// of more than 64 elements are rejected by the compiler instead of by
+    /// [`try_new`](Self::try_new) at runtime. The cursors of the iterators then
+    /// always take the `u64` fast path, so neither the dataset nor the
+    /// enumeration needs the heap, and the index-based operations, such as
+    /// `seek` and `split`, never fail for lack of bits. Counts are still
+    /// `usize`s, though: all 64 elements with the empty set make `2^64`
+    /// subsets, which neither `len` nor `par_iter` can represent. Use `new`
+    /// for larger or dynamically sized datasets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let sg = SubsetGenerator::from_array(&[1, 2, 3], false);
+    /// assert_eq!(sg.iter().count(), 7);
+    /// assert_eq!(sg.iter().seek(0b101), Some(vec![&1, &3]));
+    /// ```
+    ///
+    /// An array that is too large does not compile:
+    ///
+    /// ```compile_fail
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let sg = SubsetGenerator::from_array(&[0; 65], false);
+    /// ```
+    pub fn from_array<const N: usize>(data: &'a [T; N], with_emptyset: bool) -> Self {
+        let () = ArrayLen::<N>::FITS;
+        SubsetGenerator::new(data, with_emptyset)
+    }
+
+    /// Constructs a generator over an empty dataset that excludes the empty
+    /// set, so it yields nothing. This is also what `Default` returns, and is
+    /// useful as a placeholder in structures that hold a generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let sg: SubsetGenerator<i32> = SubsetGenerator::empty();
+    /// assert_eq!(sg.iter().next(), None);
+    /// ```
+    pub fn empty() -> SubsetGenerator<'a, T> {
+        SubsetGenerator::new(&[], false)
     }
 
     /// Returns an iterator over all the subsets of the given dataset.
@@ -91,100 +310,1658 @@ impl<'a, T> SubsetGenerator<'a, T> {
     /// let sg = SubsetGenerator::new(&data, false);
     /// assert_eq!(sg.iter().count(), 7);
     /// ```
-    pub fn iter(&self) -> SubsetIter<T> {
-        let len = self.data.len();
+    pub fn iter(&self) -> SubsetIter<'a, T> {
+        let len = self.free_len();
         SubsetIter {
-            data: &self.data,
-            set: BitVec::from_elem(len, false),
-            with_emptyset: self.with_emptyset,
+            data: self.data,
+            set: Mask::from_index(0, len),
+            back: Mask::ones(len),
+            with_emptyset: self.includes_base(),
+            emptyset: self.includes_base(),
+            constraints: self.constraints.clone(),
+            exhausted: false,
+            reported: false,
         }
     }
+
+    /// Collects all the subsets reported by `iter` into a vector, which is
+    /// allocated up front with room for exactly the `2^n` subsets (minus the
+    /// empty set if it is not included), so the collection never reallocates.
+    /// The empty set is included if (and only if) the generator includes it.
+    ///
+    /// The number of subsets doubles with every element, so this is only
+    /// feasible for small datasets: 20 elements already give over a million
+    /// vectors. Prefer iterating over the subsets when possible.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of subsets does not fit in a `usize`, or if the
+    /// allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2];
+    /// let sg = SubsetGenerator::new(&data, true);
+    ///
+    /// let subsets = sg.collect_all();
+    /// assert_eq!(subsets, vec![vec![], vec![&1], vec![&2], vec![&1, &2]]);
+    /// assert_eq!(subsets.capacity(), 4);
+    /// ```
+    pub fn collect_all(&self) -> Vec<Vec<&'a T>> {
+        let iter = self.iter();
+        let mut subsets = Vec::with_capacity(iter.size_hint().0);
+        subsets.extend(iter);
+        subsets
+    }
 }
 
-impl<'a, T> SubsetIter<'a, T> {
-    /// Adds 1 to the underlying BitVec. This effectively computes the next
-    /// subset. Returns false if all the bits were set, and so all subsets have
-    /// been exhausted.
-    fn next_set(&mut self) -> bool {
-        let mut all_set = true;
-        for i in 0..self.set.len() {
-            all_set &= self.set[i];
-        }
-        if all_set {
-            return false;
+/// Cloning a generator is cheap, as the dataset itself is shared.
+impl<'a, T> Clone for SubsetGenerator<'a, T> {
+    fn clone(&self) -> Self {
+        SubsetGenerator {
+            data: self.data,
+            with_emptyset: self.with_emptyset,
+            constraints: self.constraints.clone(),
+            size: self.size,
+            order: self.order,
+            emptyset_last: self.emptyset_last,
+            report_interval: self.report_interval,
         }
+    }
+}
 
-        for i in 0..self.set.len() {
-            if self.set[i] {
-                self.set.set(i, false);
-            } else {
-                self.set.set(i, true);
-                break;
-            }
-        }
+/// Returns an empty generator, see [`SubsetGenerator::empty`].
+impl<'a, T> Default for SubsetGenerator<'a, T> {
+    fn default() -> Self {
+        SubsetGenerator::empty()
+    }
+}
 
-        true
+/// Constructs a generator over a slice that does not include the empty set,
+/// like `SubsetGenerator::new(data, false)`.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+///
+/// let data = [1, 2, 3];
+/// let sg: SubsetGenerator<_> = data[..].into();
+/// assert_eq!(sg.iter().count(), 7);
+/// ```
+impl<'a, T> From<&'a [T]> for SubsetGenerator<'a, T> {
+    fn from(data: &'a [T]) -> Self {
+        SubsetGenerator::new(data, false)
     }
 }
 
-impl<'a, T> Iterator for SubsetIter<'a, T> {
+/// Constructs a generator over a vector that does not include the empty set,
+/// like `SubsetGenerator::new(data, false)`.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+///
+/// fn count<'a, G: Into<SubsetGenerator<'a, u8>>>(generator: G) -> usize {
+///     generator.into().iter().count()
+/// }
+///
+/// let data = vec![1, 2, 3, 4];
+/// assert_eq!(count(&data), 15);
+/// ```
+impl<'a, T> From<&'a Vec<T>> for SubsetGenerator<'a, T> {
+    fn from(data: &'a Vec<T>) -> Self {
+        SubsetGenerator::new(data, false)
+    }
+}
+
+/// Consumes the generator and returns an iterator over all the subsets. The
+/// underlying dataset cannot be consumed, so references are still returned.
+/// The iterator returns an emptyset if (and only if) the generator was
+/// configured to include the empty set. This makes the generator usable
+/// directly in a `for` loop.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+///
+/// let mut data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, true);
+/// let mut iters = 0;
+/// for _ in sg {
+///     iters += 1;
+/// }
+/// assert_eq!(iters, 8);
+///
+/// data = vec![42];
+/// // additional computations...
+/// ```
+impl<'a, T> IntoIterator for SubsetGenerator<'a, T> {
     type Item = Vec<&'a T>;
+    type IntoIter = SubsetIter<'a, T>;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.with_emptyset {
-            self.with_emptyset = false;
-            return Some(vec![]);
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Returns an iterator over all the subsets of a borrowed generator, which is
+/// equivalent to calling `iter`.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+///
+/// let data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, false);
+/// let subsets: Vec<Vec<&i32>> = (&sg).into_iter().collect();
+/// assert_eq!(subsets.len(), 7);
+///
+/// for subset in &sg {
+///     assert!(!subset.is_empty());
+/// }
+/// ```
+impl<'a, T> IntoIterator for &SubsetGenerator<'a, T> {
+    type Item = Vec<&'a T>;
+    type IntoIter = SubsetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> SubsetIter<'a, T> {
+    /// Moves the cursor to the subset with the given index and returns that
+    /// subset, so that the next call to `next` continues with the subset after
+    /// it. The index of a subset is the integer whose binary representation is
+    /// the bitmask of the subset: bit `i` (the `2^i` place) is set if and only
+    /// if the `i`-th element of the dataset is included. This is exactly the
+    /// order in which the iterator reports the subsets, so seeking takes *O(n)*
+    /// instead of stepping through all the subsets in between.
+    ///
+    /// Index 0 denotes the empty set. If the generator was configured without
+    /// the empty set, `seek(0)` returns the first non-empty subset instead.
+    /// Returns `None`, and leaves the iterator untouched, if `index >= 2^n`.
+    /// Seeking restores the back of the iterator to the last subset, so any
+    /// subsets previously taken with `next_back` are reported again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, true);
+    /// let mut iter = sg.iter();
+    ///
+    /// assert_eq!(iter.seek(5), Some(vec![&1, &3]));
+    /// assert_eq!(iter.next(), Some(vec![&2, &3]));
+    /// assert_eq!(iter.seek(0), Some(vec![]));
+    /// assert_eq!(iter.seek(8), None);
+    /// ```
+    pub fn seek(&mut self, index: u64) -> Option<Vec<&'a T>> {
+        let len = self.set.len();
+        if len < 64 && index >> len != 0 {
+            return None;
         }
 
-        if self.next_set() {
-            let mut result = Vec::new();
-            for i in 0..self.set.len() {
-                if self.set[i] {
-                    result.push(&self.data[i]);
-                }
-            }
-            Some(result)
-        } else {
-            None
+        self.seek_mask(Mask::from_index(index, len))
+    }
+
+    /// Moves the cursor to `set`, which must have the length of the cursor,
+    /// and returns the subset it selects. See [`seek`](Self::seek).
+    fn seek_mask(&mut self, set: Mask) -> Option<Vec<&'a T>> {
+        let empty = set.lowest_set().is_none();
+        self.back = Mask::ones(set.len());
+        self.set = set;
+        self.with_emptyset = false;
+        self.exhausted = false;
+        self.reported = false;
+
+        if empty && !self.emptyset {
+            return self.next();
         }
+        Some(self.subset())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Rewinds the iterator to the start of the enumeration, so that it reports
+    /// all the subsets again, including the empty set if (and only if) the
+    /// generator was configured to include it. The cursors are cleared in
+    /// place, so sweeping the powerset several times needs neither the
+    /// generator nor any new allocation. The iterator is rewound to the full
+    /// enumeration of `iter`, also if it was created by `split` or `resume`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, true);
+    /// let mut iter = sg.iter();
+    ///
+    /// assert_eq!(iter.by_ref().count(), 8);
+    /// iter.reset();
+    /// assert_eq!(iter.next(), Some(vec![]));
+    /// assert_eq!(iter.count(), 7);
+    /// ```
+    pub fn reset(&mut self) {
+        self.set.fill(false);
+        self.back.fill(true);
+        self.with_emptyset = self.emptyset;
+        self.exhausted = false;
+        self.reported = false;
+    }
 
-    #[test]
-    fn small_set_without_empty() {
-        let data = vec![(1, 0), (2, 0), (3, 0), (4, 0)];
-        let small_generator = SubsetGenerator::new(&data, false);
-        let mut iters = 0;
-        for _ in small_generator.iter() {
-            iters += 1;
+    /// Decides at runtime whether the empty set is reported, overriding the
+    /// configuration of the generator, without rebuilding it. This is only
+    /// honored before the first subset is reported from the front of the
+    /// iterator; afterwards the call has no effect and returns false. It also
+    /// has no effect if elements are required, as the base subset is then not
+    /// empty and always reported. The choice is kept by `reset`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.iter();
+    ///
+    /// assert!(iter.set_emptyset(true));
+    /// assert_eq!(iter.next(), Some(vec![]));
+    /// assert!(!iter.set_emptyset(false));
+    /// assert_eq!(iter.count(), 3);
+    /// ```
+    pub fn set_emptyset(&mut self, include: bool) -> bool {
+        let required = self
+            .constraints
+            .as_ref()
+            .is_some_and(Constraints::has_required);
+        let started = self.with_emptyset != self.emptyset || self.set.count_ones() > 0;
+        if required || started {
+            return false;
         }
-        assert_eq!(iters, 15);
+        self.with_emptyset = include;
+        self.emptyset = include;
+        true
     }
 
-    #[test]
-    fn small_set_with_empty() {
-        let data = vec![(1, 0), (2, 0), (3, 0), (4, 0)];
-        let small_generator = SubsetGenerator::new(&data, true);
-        let mut iters = 0;
-        for _ in small_generator.iter() {
-            iters += 1;
+    /// Returns the bitmask of the cursor, which is the mask of the subset that
+    /// was reported last: bit `i` is set if and only if the `i`-th element of
+    /// the dataset is included. Before the first subset is reported, and while
+    /// the empty set is still pending, no bits are set. With constraints, the
+    /// bits only span the free elements, as for [`seek`](Self::seek).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.iter();
+    /// iter.next();
+    /// iter.next();
+    /// iter.next();
+    ///
+    /// let mask = iter.current_mask();
+    /// assert!(mask[0] && mask[1] && !mask[2]);
+    /// ```
+    pub fn current_mask(&self) -> BitVec {
+        self.set.to_bitvec()
+    }
+
+    /// Returns true if the element at index `i` of the dataset is in the subset
+    /// that was reported last, by reading the cursor directly instead of
+    /// scanning the subset. As for [`current_mask`](Self::current_mask), the
+    /// cursor selects no free elements before the first subset is reported;
+    /// with constraints, the required elements are always selected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.iter();
+    /// iter.nth(2);
+    ///
+    /// assert!(iter.contains_index(0) && iter.contains_index(1));
+    /// assert!(!iter.contains_index(2));
+    /// ```
+    pub fn contains_index(&self, i: usize) -> bool {
+        assert!(
+            i < self.data.len(),
+            "index {} is out of range for {} elements",
+            i,
+            self.data.len()
+        );
+        match &self.constraints {
+            Some(constraints) => constraints.selects(i, Some(&self.set)),
+            None => self.set.get(i),
         }
-        assert_eq!(iters, 16);
     }
 
-    #[test]
-    fn into_iter_test() {
-        let mut data = vec![(1, 0), (2, 0), (3, 0), (4, 0)];
-        let small_generator = SubsetGenerator::new(&data, false);
-        let mut iters = 0;
-        for _ in small_generator.into_iter() {
-            iters += 1;
+    /// Returns the number of elements of the subset that was reported last,
+    /// which is the number of set bits of the cursor plus the number of
+    /// required elements, without materializing the subset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.iter();
+    ///
+    /// assert_eq!(iter.selected_count(), 0);
+    /// iter.nth(6);
+    /// assert_eq!(iter.selected_count(), 3);
+    /// ```
+    pub fn selected_count(&self) -> usize {
+        let required = self
+            .constraints
+            .as_ref()
+            .map_or(0, Constraints::required_len);
+        self.set.count_ones() + required
+    }
+
+    /// Returns the bitmask of the subset that the next call to `next` will
+    /// report, in the format of [`current_mask`](Self::current_mask), or
+    /// `None` if the iterator is exhausted. The cursor is not moved and the
+    /// subset is not materialized, so branch-and-bound code can inspect the
+    /// upcoming subset before deciding to skip ahead, without the clone that
+    /// `Peekable` would make.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.iter();
+    /// iter.next();
+    ///
+    /// let mask = iter.peek_next_mask().unwrap();
+    /// assert!(!mask[0] && mask[1] && !mask[2]);
+    /// assert_eq!(iter.next(), Some(vec![&2]));
+    /// ```
+    pub fn peek_next_mask(&self) -> Option<BitVec> {
+        if self.with_emptyset {
+            return Some(BitVec::from_elem(self.set.len(), false));
         }
-        assert_eq!(iters, 15);
-        data[0] = (2, 0);
+        if !self.set.less_than(&self.back) {
+            return None;
+        }
+        let mut next = self.set.clone();
+        next.increment();
+        Some(next.to_bitvec())
+    }
+
+    /// Returns the index in `[0, 2^n)` of the subset that was reported last,
+    /// which is the integer value of [`current_mask`](Self::current_mask).
+    /// Passing it to [`seek`](Self::seek) on a fresh iterator continues the
+    /// enumeration from the same position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index does not fit in a `u64`, which can only happen for
+    /// datasets of more than 64 elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, true);
+    /// let mut iter = sg.iter();
+    /// assert_eq!(iter.current_index(), 0);
+    ///
+    /// iter.nth(5);
+    /// assert_eq!(iter.current_index(), 5);
+    /// ```
+    pub fn current_index(&self) -> u64 {
+        self.set.index()
+    }
+
+    /// Returns the fraction in `[0.0, 1.0]` of the subsets that have already
+    /// been consumed, from either end, which is convenient for a progress bar.
+    /// The fraction is computed from the cursors relative to all `2^n` subsets,
+    /// so it takes *O(n)* time and works for any dataset size, but it is an
+    /// estimate only: it is meaningful for the full iterator returned by
+    /// `iter`, whereas for an iterator returned by `split` it does not start at
+    /// 0.0. It is exactly 1.0 once the iterator is exhausted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, true);
+    /// let mut iter = sg.iter();
+    /// assert_eq!(iter.progress(), 0.0);
+    ///
+    /// iter.nth(3);
+    /// assert_eq!(iter.progress(), 0.5);
+    ///
+    /// iter.by_ref().count();
+    /// assert_eq!(iter.progress(), 1.0);
+    /// ```
+    pub fn progress(&self) -> f64 {
+        let unit = mask::exp2(-(self.set.len() as i32));
+        let total = 1.0 - !self.emptyset as u8 as f64 * unit;
+        let remaining =
+            self.back.fraction() - self.set.fraction() + self.with_emptyset as u8 as f64 * unit;
+        if remaining <= 0.0 || total <= 0.0 {
+            return 1.0;
+        }
+        (1.0 - remaining / total).clamp(0.0, 1.0)
+    }
+
+    /// Skips the next `k` subsets without generating them, and returns how
+    /// many subsets were actually skipped. This is less than `k` only if the
+    /// iterator ran out of subsets, in which case it is now exhausted. The
+    /// cursor is moved forward by `k` at once, so skipping takes *O(n)* time
+    /// regardless of `k`. Where [`seek`](Self::seek) jumps to an absolute
+    /// index, this skips relative to the current position, and it also
+    /// respects subsets already taken from the back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, true);
+    /// let mut iter = sg.iter();
+    ///
+    /// assert_eq!(iter.advance_by(5), 5);
+    /// assert_eq!(iter.next(), Some(vec![&1, &3]));
+    /// assert_eq!(iter.advance_by(5), 2);
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn advance_by(&mut self, k: usize) -> usize {
+        if k == 0 {
+            return 0;
+        }
+        self.reported = false;
+        if self.with_emptyset {
+            self.with_emptyset = false;
+            return 1 + self.advance_by(k - 1);
+        }
+
+        let skipped = match self.set.distance(&self.back) {
+            Some(remaining) => k.min(remaining),
+            None => k,
+        };
+        self.set.add(skipped);
+        skipped
+    }
+
+    /// Skips ahead to the next subset with exactly `k` elements and returns it,
+    /// or returns `None` if no remaining subset has `k` elements, in which
+    /// case the iterator is exhausted. The cursor jumps directly between masks
+    /// with `k` set bits, like [`combinations`](SubsetGenerator::combinations)
+    /// does, instead of incrementing one mask at a time, so that the plain
+    /// iterator can be mixed with cardinality-targeted steps. With
+    /// constraints, `k` counts the free elements only, and the required
+    /// elements are added on top.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3, 4];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.iter();
+    ///
+    /// assert_eq!(iter.next_with_popcount(2), Some(vec![&1, &2]));
+    /// assert_eq!(iter.next(), Some(vec![&3]));
+    /// assert_eq!(iter.next_with_popcount(3), Some(vec![&1, &2, &3]));
+    /// assert_eq!(iter.next_with_popcount(1), Some(vec![&4]));
+    /// assert_eq!(iter.next_with_popcount(1), None);
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn next_with_popcount(&mut self, k: usize) -> Option<Vec<&'a T>> {
+        self.reported = false;
+        if self.with_emptyset {
+            self.with_emptyset = false;
+            if k == 0 {
+                let mut result = Vec::new();
+                self.select_into(None, &mut result);
+                return Some(result);
+            }
+        }
+
+        if !self.exhausted
+            && self.set.less_than(&self.back)
+            && self.set.next_with_ones(k)
+            && !self.back.less_than(&self.set)
+        {
+            Some(self.subset())
+        } else {
+            self.set.clone_from(&self.back);
+            self.exhausted = true;
+            None
+        }
+    }
+
+    /// Advances the iterator like `next`, but writes the subset into `buf`
+    /// instead of allocating a new vector. The buffer is cleared first, and
+    /// refilled with the elements of the next subset. Returns false, leaving
+    /// the buffer empty, once all subsets have been exhausted.
+    ///
+    /// Once the buffer has grown to hold the largest subset, enumerating the
+    /// remaining subsets performs no allocations at all. The contents of the
+    /// buffer are only meaningful until the next call, which overwrites them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.iter();
+    /// let mut buf = Vec::with_capacity(data.len());
+    /// let mut total = 0;
+    /// while iter.next_into(&mut buf) {
+    ///     total += buf.iter().copied().sum::<i32>();
+    /// }
+    /// assert_eq!(total, 24);
+    /// ```
+    pub fn next_into(&mut self, buf: &mut Vec<&'a T>) -> bool {
+        buf.clear();
+        self.reported = true;
+        if self.with_emptyset {
+            self.with_emptyset = false;
+            self.select_into(None, buf);
+            return true;
+        }
+
+        if self.next_set() {
+            self.select_into(Some(&self.set), buf);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Folds all the remaining subsets into an accumulator, like
+    /// `Iterator::fold`, but passes each subset to `f` as a slice of a single
+    /// reused buffer (see [`next_into`](Self::next_into)). This makes folds
+    /// over all subsets, such as counting or scoring, free of per-subset
+    /// allocations. A subset that has to be kept can be copied with `to_vec`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    ///
+    /// let best = sg.iter().fold_subsets(0, |best, s| best.max(s.len()));
+    /// assert_eq!(best, 3);
+    /// ```
+    pub fn fold_subsets<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, &[&'a T]) -> B,
+    {
+        let mut buf = Vec::with_capacity(self.data.len());
+        let mut acc = init;
+        while self.next_into(&mut buf) {
+            acc = f(acc, &buf);
+        }
+        acc
+    }
+
+    /// Collects the elements selected by the current state of the cursor.
+    fn subset(&self) -> Vec<&'a T> {
+        let mut result = Vec::new();
+        self.select_into(Some(&self.set), &mut result);
+        result
+    }
+
+    /// Returns the element selected by bit `bit` of the cursor.
+    fn element(&self, bit: usize) -> &'a T {
+        match &self.constraints {
+            Some(constraints) => &self.data[constraints.position(bit)],
+            None => &self.data[bit],
+        }
+    }
+
+    /// Appends the elements that are not selected by `mask` to `buf`, or all
+    /// elements except the required ones if `mask` is `None`.
+    fn complement_into(&self, mask: Option<&Mask>, buf: &mut Vec<&'a T>) {
+        for (i, element) in self.data.iter().enumerate() {
+            let selected = match &self.constraints {
+                Some(constraints) => constraints.selects(i, mask),
+                None => mask.is_some_and(|mask| mask.get(i)),
+            };
+            if !selected {
+                buf.push(element);
+            }
+        }
+    }
+
+    /// Appends the elements selected by `mask` to `buf`, or only the required
+    /// elements (if any) if `mask` is `None`.
+    fn select_into(&self, mask: Option<&Mask>, buf: &mut Vec<&'a T>) {
+        match (&self.constraints, mask) {
+            (Some(constraints), mask) => constraints.collect_into(self.data, mask, buf),
+            (None, Some(mask)) => mask.collect_into(self.data, buf),
+            (None, None) => {}
+        }
+    }
+
+    /// Adds 1 to the cursor. This effectively computes the next subset.
+    /// Returns false if the cursor reached the back cursor, or if all the bits
+    /// were set, and so all subsets have been exhausted.
+    fn next_set(&mut self) -> bool {
+        // Without free elements, the only subset is the base subset, which is
+        // reported separately.
+        mask::step_forward(&mut self.set, &self.back, &mut self.exhausted)
+    }
+
+    /// Subtracts 1 from the back cursor, which is the highest mask that has not
+    /// been reported yet. Returns false if the back cursor already met the
+    /// front cursor, and so all non-empty subsets have been exhausted.
+    fn prev_set(&mut self) -> bool {
+        mask::step_backward(&self.set, &mut self.back, &mut self.exhausted)
+    }
+
+    /// Returns the number of subsets that have not been reported yet, or
+    /// `None` if that number does not fit in a `usize`. The subsets still to
+    /// come are exactly the masks above the cursor up to and including the
+    /// back cursor, so their number is the difference between the two (plus
+    /// the pending empty set, if any).
+    fn remaining(&self) -> Option<usize> {
+        self.set
+            .distance(&self.back)?
+            .checked_add(self.with_emptyset as usize)
+    }
+}
+
+/// Cloning an iterator snapshots its position; the clone and the original then
+/// continue independently. The dataset itself is shared, so `T` does not need
+/// to implement `Clone`.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+///
+/// let data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, false);
+/// let mut iter = sg.iter();
+/// iter.next();
+///
+/// let fork = iter.clone();
+/// assert_eq!(iter.count(), 6);
+/// assert_eq!(fork.count(), 6);
+/// ```
+impl<'a, T> Clone for SubsetIter<'a, T> {
+    fn clone(&self) -> Self {
+        SubsetIter {
+            data: self.data,
+            set: self.set.clone(),
+            back: self.back.clone(),
+            with_emptyset: self.with_emptyset,
+            emptyset: self.emptyset,
+            constraints: self.constraints.clone(),
+            exhausted: self.exhausted,
+            reported: self.reported,
+        }
+    }
+}
+
+/// Shows the current position of the iterator: the mask of the last reported
+/// subset as a binary number (with the first element as the lowest bit), and
+/// the number of subsets that remain (`None` if it does not fit in a `usize`).
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+///
+/// let data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, false);
+/// let mut iter = sg.iter();
+/// iter.next();
+/// iter.next();
+///
+/// let debug = format!("{:?}", iter);
+/// assert!(debug.contains("mask: 010"));
+/// assert!(debug.contains("remaining: Some(5)"));
+/// ```
+impl<'a, T: fmt::Debug> fmt::Debug for SubsetIter<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SubsetIter")
+            .field("data", &self.data)
+            .field("mask", &format_args!("{:b}", self.set))
+            .field("remaining", &self.remaining())
+            .field("constraints", &self.constraints)
+            .finish()
+    }
+}
+
+impl<'a, T> Iterator for SubsetIter<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.reported = true;
+        if self.with_emptyset {
+            self.with_emptyset = false;
+            let mut result = Vec::new();
+            self.select_into(None, &mut result);
+            return Some(result);
+        }
+
+        if self.next_set() {
+            Some(self.subset())
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining() {
+            Some(remaining) => (remaining, Some(remaining)),
+            None => (usize::MAX, None),
+        }
+    }
+
+    /// Returns the number of remaining subsets directly from the cursors,
+    /// without enumerating them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of remaining subsets does not fit in a `usize`,
+    /// like `len`. Counting them one by one would never finish anyway.
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    /// Skips the first `n` subsets with [`advance_by`](SubsetIter::advance_by)
+    /// instead of generating them.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.advance_by(n);
+        self.next()
+    }
+
+    /// Returns the last remaining subset directly from the back cursor, which
+    /// is the full set (or, with constraints, all free and required elements)
+    /// unless the back of the iterator was already consumed.
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+/// Reports the subsets from the largest mask down, so the full set comes first
+/// and the empty set (if configured) comes last. The front and back of the
+/// iterator can be mixed freely; they stop when they meet.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+///
+/// let data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, true);
+/// let mut iter = sg.iter().rev();
+///
+/// assert_eq!(iter.next(), Some(vec![&1, &2, &3]));
+/// assert_eq!(iter.next(), Some(vec![&2, &3]));
+/// assert_eq!(iter.last(), Some(vec![]));
+/// ```
+impl<'a, T> DoubleEndedIterator for SubsetIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.reported = false;
+        let mut subset = Vec::new();
+        self.select_into(Some(&self.back), &mut subset);
+        if self.prev_set() {
+            return Some(subset);
+        }
+
+        if self.with_emptyset {
+            self.with_emptyset = false;
+            subset.clear();
+            self.select_into(None, &mut subset);
+            return Some(subset);
+        }
+        None
+    }
+}
+
+/// The number of subsets is known up front: `2^n` with the empty set and
+/// `2^n - 1` without it.
+///
+/// # Panics
+///
+/// `len` panics if the number of remaining subsets does not fit in a `usize`,
+/// which can only happen for datasets with at least `usize::BITS` elements.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+///
+/// let data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, false);
+/// let mut iter = sg.iter();
+/// assert_eq!(iter.len(), 7);
+///
+/// iter.next();
+/// assert_eq!(iter.len(), 6);
+/// ```
+impl<'a, T> ExactSizeIterator for SubsetIter<'a, T> {
+    fn len(&self) -> usize {
+        self.remaining()
+            .expect("the number of remaining subsets does not fit in a usize")
+    }
+}
+
+/// Once the iterator returned `None`, from either end, it keeps returning
+/// `None`: the exhaustion is remembered, and is only undone by explicitly
+/// repositioning the iterator, e.g. with `reset` or `seek`.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+///
+/// let data = vec![1, 2];
+/// let sg = SubsetGenerator::new(&data, false);
+/// let mut iter = sg.iter().fuse();
+///
+/// assert_eq!(iter.by_ref().count(), 3);
+/// assert_eq!(iter.next(), None);
+/// ```
+impl<'a, T> FusedIterator for SubsetIter<'a, T> {}
+
+/// Appends references to the elements of `data` whose bits are set in `set` to
+/// `buf`.
+fn subset_into<'a, T>(data: &'a [T], set: &BitVec, buf: &mut Vec<&'a T>) {
+    for i in 0..set.len() {
+        if set[i] {
+            buf.push(&data[i]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_set_without_empty() {
+        let data = vec![(1, 0), (2, 0), (3, 0), (4, 0)];
+        let small_generator = SubsetGenerator::new(&data, false);
+        let mut iters = 0;
+        for _ in small_generator.iter() {
+            iters += 1;
+        }
+        assert_eq!(iters, 15);
+    }
+
+    #[test]
+    fn small_set_with_empty() {
+        let data = vec![(1, 0), (2, 0), (3, 0), (4, 0)];
+        let small_generator = SubsetGenerator::new(&data, true);
+        let mut iters = 0;
+        for _ in small_generator.iter() {
+            iters += 1;
+        }
+        assert_eq!(iters, 16);
+    }
+
+    #[test]
+    fn into_iter_test() {
+        let mut data = vec![(1, 0), (2, 0), (3, 0), (4, 0)];
+        let small_generator = SubsetGenerator::new(&data, false);
+        let mut iters = 0;
+        for _ in small_generator {
+            iters += 1;
+        }
+        assert_eq!(iters, 15);
+        data[0] = (2, 0);
+    }
+
+    #[test]
+    fn medium_set_without_empty() {
+        let data: Vec<u32> = (0..16).collect();
+        let medium_generator = SubsetGenerator::new(&data, false);
+        let mut iter = medium_generator.iter();
+        let mut iters = 0;
+        while iter.next().is_some() {
+            iters += 1;
+        }
+        assert_eq!(iters, (1 << 16) - 1);
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn next_into_matches_next() {
+        let data = vec![1, 2, 3, 4, 5];
+        let small_generator = SubsetGenerator::new(&data, true);
+        let mut iter = small_generator.iter();
+        let mut buf = Vec::new();
+        for expected in small_generator.iter() {
+            assert!(iter.next_into(&mut buf));
+            assert_eq!(buf, expected);
+        }
+        assert!(!iter.next_into(&mut buf));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn next_into_does_not_allocate() {
+        let data: Vec<u32> = (0..12).collect();
+        let medium_generator = SubsetGenerator::new(&data, false);
+        let mut iter = medium_generator.iter();
+        let mut buf = Vec::with_capacity(data.len());
+        let storage = buf.as_ptr();
+        let mut iters = 0;
+        while iter.next_into(&mut buf) {
+            assert_eq!(buf.as_ptr(), storage);
+            iters += 1;
+        }
+        assert_eq!(iters, (1 << 12) - 1);
+        assert_eq!(buf.capacity(), data.len());
+    }
+
+    #[test]
+    fn slice_like_containers() {
+        let array = [1, 2, 3];
+        let boxed: Box<[i32]> = Box::new([1, 2, 3]);
+        let vec = vec![1, 2, 3];
+        let expected: Vec<_> = SubsetGenerator::new(&vec, true).iter().collect();
+        assert_eq!(SubsetGenerator::new(&array, true).iter().collect::<Vec<_>>(), expected);
+        assert_eq!(SubsetGenerator::new(&boxed, true).iter().collect::<Vec<_>>(), expected);
+        assert_eq!(SubsetGenerator::new(&vec[..], true).iter().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn empty_dataset_with_empty() {
+        let data: Vec<i32> = vec![];
+        let empty_generator = SubsetGenerator::new(&data, true);
+        assert_eq!(
+            empty_generator.iter().collect::<Vec<_>>(),
+            vec![Vec::<&i32>::new()]
+        );
+        assert_eq!(empty_generator.iter().count(), 1);
+        assert_eq!(empty_generator.iter().len(), 1);
+        assert_eq!(empty_generator.iter().rev().count(), 1);
+
+        let mut iter = empty_generator.iter();
+        assert_eq!(iter.next(), Some(vec![]));
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn empty_dataset_without_empty() {
+        let data: Vec<i32> = vec![];
+        let empty_generator = SubsetGenerator::new(&data, false);
+        assert_eq!(empty_generator.iter().count(), 0);
+        assert_eq!(empty_generator.iter().len(), 0);
+        assert_eq!(empty_generator.iter().next_back(), None);
+
+        let mut iter = empty_generator.iter();
+        let mut buf = Vec::new();
+        assert!(!iter.next_into(&mut buf));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn len_without_empty() {
+        let data = vec![1, 2, 3, 4];
+        let small_generator = SubsetGenerator::new(&data, false);
+        let mut iter = small_generator.iter();
+        for remaining in (0..15).rev() {
+            assert!(iter.next().is_some());
+            assert_eq!(iter.len(), remaining);
+            assert_eq!(iter.size_hint(), (remaining, Some(remaining)));
+        }
+        assert!(iter.next().is_none());
+        assert_eq!(iter.len(), 0);
+    }
+
+    #[test]
+    fn len_with_empty() {
+        let data = vec![1, 2, 3, 4];
+        let small_generator = SubsetGenerator::new(&data, true);
+        let mut iter = small_generator.iter();
+        assert_eq!(iter.len(), 16);
+        iter.next();
+        assert_eq!(iter.len(), 15);
+        assert_eq!(iter.count(), 15);
+    }
+
+    #[test]
+    fn rev_same_subsets() {
+        let data = vec![1, 2, 3, 4, 5];
+        let small_generator = SubsetGenerator::new(&data, true);
+        let mut forward: Vec<_> = small_generator.iter().collect();
+        let backward: Vec<_> = small_generator.iter().rev().collect();
+        forward.reverse();
+        assert_eq!(forward, backward);
+    }
+
+    #[test]
+    fn rev_starts_with_full_set() {
+        let data = vec![(1, 0), (2, 0), (3, 0), (4, 0)];
+        let small_generator = SubsetGenerator::new(&data, false);
+        let mut iter = small_generator.iter().rev();
+        assert_eq!(iter.next(), Some(data.iter().collect()));
+        assert_eq!(iter.count(), 14);
+    }
+
+    #[test]
+    fn front_and_back_meet() {
+        let data = vec![1, 2, 3, 4];
+        let small_generator = SubsetGenerator::new(&data, true);
+        let mut iter = small_generator.iter();
+        let mut seen = Vec::new();
+        while let Some(subset) = iter.next() {
+            seen.push(subset);
+            if let Some(subset) = iter.next_back() {
+                seen.push(subset);
+            }
+            assert_eq!(iter.len(), 16 - seen.len());
+        }
+        assert_eq!(iter.next_back(), None);
+        seen.sort();
+        let mut expected: Vec<_> = small_generator.iter().collect();
+        expected.sort();
+        assert_eq!(seen, expected);
+    }
+
+    #[test]
+    fn clone_continues_independently() {
+        struct NotClone(u32);
+        let data: Vec<NotClone> = (0..6).map(NotClone).collect();
+        let small_generator = SubsetGenerator::new(&data, true);
+        let mut iter = small_generator.iter();
+        for _ in 0..5 {
+            iter.next();
+        }
+
+        let mut fork = iter.clone();
+        assert_eq!(fork.next().map(|s| s.len()), Some(2));
+        let remaining: Vec<Vec<u32>> = iter.map(|s| s.iter().map(|x| x.0).collect()).collect();
+        let forked: Vec<Vec<u32>> = fork.map(|s| s.iter().map(|x| x.0).collect()).collect();
+        assert_eq!(remaining.len(), 59);
+        assert_eq!(&remaining[1..], &forked[..]);
+    }
+
+    #[test]
+    fn seek_matches_iteration() {
+        let data = vec![1, 2, 3, 4, 5];
+        let small_generator = SubsetGenerator::new(&data, true);
+        for (index, expected) in small_generator.iter().enumerate() {
+            let mut iter = small_generator.iter();
+            assert_eq!(iter.seek(index as u64), Some(expected));
+
+            let mut reference = small_generator.iter();
+            reference.nth(index);
+            assert!(iter.eq(reference));
+        }
+    }
+
+    #[test]
+    fn seek_without_empty() {
+        let data = vec![1, 2, 3];
+        let small_generator = SubsetGenerator::new(&data, false);
+        let mut iter = small_generator.iter();
+        assert_eq!(iter.seek(0), Some(vec![&1]));
+        assert_eq!(iter.seek(7), Some(vec![&1, &2, &3]));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.seek(8), None);
+        assert_eq!(iter.seek(3), Some(vec![&1, &2]));
+        assert_eq!(iter.len(), 4);
+    }
+
+    #[test]
+    fn large_set_ends() {
+        let data: Vec<usize> = (0..70).collect();
+        let large_generator = SubsetGenerator::new(&data, false);
+        let mut iter = large_generator.iter();
+        assert_eq!(iter.next(), Some(vec![&0]));
+        assert_eq!(iter.next(), Some(vec![&1]));
+        assert_eq!(iter.next(), Some(vec![&0, &1]));
+        assert_eq!(iter.next_back(), Some(data.iter().collect()));
+        assert_eq!(iter.next_back(), Some(data[1..].iter().collect()));
+        assert_eq!(iter.seek(1 << 63), Some(vec![&63]));
+        assert_eq!(iter.next(), Some(vec![&0, &63]));
+    }
+
+    #[test]
+    fn count_without_enumerating() {
+        let data: Vec<u32> = (0..20).collect();
+        let medium_generator = SubsetGenerator::new(&data, false);
+        assert_eq!(medium_generator.iter().count(), (1 << 20) - 1);
+
+        let data: Vec<u32> = (0..40).collect();
+        let large_generator = SubsetGenerator::new(&data, true);
+        let mut iter = large_generator.iter();
+        iter.next();
+        iter.next_back();
+        assert_eq!(iter.count(), (1 << 40) - 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit in a usize")]
+    fn count_overflow() {
+        let data: Vec<u32> = (0..usize::BITS + 1).collect();
+        SubsetGenerator::new(&data, false).iter().count();
+    }
+
+    #[test]
+    fn last_without_enumerating() {
+        let data: Vec<u32> = (0..24).collect();
+        let medium_generator = SubsetGenerator::new(&data, false);
+        assert_eq!(medium_generator.iter().last(), Some(data.iter().collect()));
+
+        let constrained = SubsetGenerator::new(&data, false).with_forbidden(&[3]);
+        let last = constrained.iter().last().unwrap();
+        assert_eq!(last.len(), 23);
+        assert!(!last.contains(&&3));
+
+        let empty: Vec<u32> = vec![];
+        let empty_generator = SubsetGenerator::new(&empty, true);
+        assert_eq!(empty_generator.iter().last(), Some(vec![]));
+        let empty_generator = SubsetGenerator::new(&empty, false);
+        assert_eq!(empty_generator.iter().last(), None);
+    }
+
+    #[test]
+    fn debug_shows_mask() {
+        let data = vec![(1, 0), (2, 0), (3, 0), (4, 0)];
+        let small_generator = SubsetGenerator::new(&data, true);
+        let mut iter = small_generator.iter();
+        assert!(format!("{:?}", iter).contains("mask: 0000"));
+        iter.nth(6);
+        let debug = format!("{:?}", iter);
+        assert!(debug.contains("mask: 0110"));
+        assert!(debug.contains("remaining: Some(9)"));
+        assert!(format!("{:?}", small_generator).contains("with_emptyset: true"));
+    }
+
+    #[test]
+    fn advance_across_midpoint() {
+        let data: Vec<usize> = (0..10).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        let all: Vec<_> = generator.iter().collect();
+
+        let mut iter = generator.iter();
+        assert_eq!(iter.advance_by(300), 300);
+        assert_eq!(iter.next().as_ref(), Some(&all[300]));
+        assert_eq!(iter.advance_by(500), 500);
+        assert_eq!(iter.next().as_ref(), Some(&all[801]));
+        assert_eq!(iter.len(), all.len() - 802);
+
+        let mut iter = generator.iter();
+        assert_eq!(iter.nth(511), Some(all[511].clone()));
+        assert_eq!(iter.nth(1), Some(all[513].clone()));
+        iter.next_back();
+        assert_eq!(iter.advance_by(1000), all.len() - 515);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.advance_by(1), 0);
+
+        let generator = SubsetGenerator::new(&data, true);
+        let mut iter = generator.iter();
+        assert_eq!(iter.advance_by(512), 512);
+        assert_eq!(iter.next(), Some(all[511].clone()));
+    }
+
+    #[test]
+    fn advance_large_set() {
+        let data: Vec<usize> = (0..100).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        let mut iter = generator.iter();
+        assert_eq!(iter.advance_by(usize::MAX), usize::MAX);
+        assert_eq!(iter.next(), Some(vec![&(usize::BITS as usize)]));
+    }
+
+    #[test]
+    fn current_position() {
+        let data: Vec<usize> = (0..10).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        let mut iter = generator.iter();
+        for expected in 1..100 {
+            let subset = iter.next().unwrap();
+            assert_eq!(iter.current_index(), expected);
+
+            let mask = iter.current_mask();
+            let selected: Vec<&usize> = data.iter().filter(|&&i| mask[i]).collect();
+            assert_eq!(selected, subset);
+        }
+
+        let mut resumed = generator.iter();
+        resumed.seek(iter.current_index());
+        assert!(resumed.eq(iter));
+    }
+
+    #[test]
+    fn progress_fraction() {
+        let data: Vec<usize> = (0..8).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let mut iter = generator.iter();
+            let total = iter.len() as f64;
+            let mut consumed = 0.0;
+            let mut last = 0.0;
+            loop {
+                let progress = iter.progress();
+                assert!((progress - consumed / total).abs() < 1e-12);
+                assert!(progress >= last);
+                last = progress;
+                if iter.next().is_none() {
+                    break;
+                }
+                consumed += 1.0;
+            }
+            assert_eq!(iter.progress(), 1.0);
+        }
+
+        let mut iter = SubsetGenerator::new(&data, false).iter();
+        iter.next_back();
+        assert!(iter.progress() > 0.0);
+
+        let empty: Vec<u32> = vec![];
+        assert_eq!(SubsetGenerator::new(&empty, false).iter().progress(), 1.0);
+        assert_eq!(SubsetGenerator::new(&empty, true).iter().progress(), 0.0);
+    }
+
+    #[test]
+    fn progress_large_set() {
+        let data: Vec<usize> = (0..2000).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        let mut iter = generator.iter();
+        assert_eq!(iter.progress(), 0.0);
+        iter.seek(1 << 63);
+        assert!(iter.progress() < 1e-12);
+        iter.next_back();
+        assert!(iter.progress() >= 0.0 && iter.progress() < 1e-12);
+    }
+
+    #[test]
+    fn reset_repeats_enumeration() {
+        for &len in &[6, 70] {
+            let data: Vec<usize> = (0..len).collect();
+            for &with_emptyset in &[false, true] {
+                let generator = SubsetGenerator::new(&data, with_emptyset);
+                let mut iter = generator.iter();
+                let first: Vec<_> = iter.by_ref().take(100).collect();
+                iter.next_back();
+                iter.reset();
+                let second: Vec<_> = iter.by_ref().take(100).collect();
+                assert_eq!(first, second);
+
+                iter.reset();
+                assert_eq!(iter.next_back(), generator.iter().next_back());
+            }
+        }
+
+        let data: Vec<usize> = (0..8).collect();
+        let generator = SubsetGenerator::new(&data, false).with_required(&[2]);
+        let mut iter = generator.split(3).pop().unwrap();
+        iter.reset();
+        assert_eq!(iter.by_ref().count(), 128);
+        iter.reset();
+        assert_eq!(iter.count(), 128);
+    }
+
+    #[test]
+    fn twelve_elements_match_unrank() {
+        let data: Vec<usize> = (0..12).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        let mut count = 0;
+        for (index, subset) in generator.iter().enumerate() {
+            let mask = rank::unrank(index as u64 + 1, data.len());
+            let expected: Vec<&usize> = data.iter().filter(|&&i| mask[i]).collect();
+            assert_eq!(subset, expected);
+            count += 1;
+        }
+        assert_eq!(count, (1 << 12) - 1);
+    }
+
+    #[test]
+    fn default_is_empty() {
+        let generator: SubsetGenerator<String> = Default::default();
+        assert_eq!(generator.iter().count(), 0);
+        assert_eq!(generator.iter().len(), 0);
+        assert_eq!(SubsetGenerator::<u8>::empty().into_iter().next(), None);
+    }
+
+    #[test]
+    fn fold_subsets_sizes() {
+        let data: Vec<u32> = (0..14).collect();
+        let generator = SubsetGenerator::new(&data, true);
+        let total = generator
+            .iter()
+            .fold_subsets(0u64, |acc, s| acc + s.len() as u64);
+        assert_eq!(total, 14 * (1 << 13));
+
+        let mut iter = generator.iter();
+        iter.advance_by(100);
+        let rest = iter.fold_subsets(0usize, |acc, _| acc + 1);
+        assert_eq!(rest, (1 << 14) - 100);
+    }
+
+    #[test]
+    fn set_emptyset_before_first() {
+        let data = vec![1, 2, 3];
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            for &include in &[false, true] {
+                let mut iter = generator.iter();
+                assert!(iter.set_emptyset(include));
+                assert_eq!(iter.len(), 7 + include as usize);
+                assert_eq!(iter.next().unwrap().is_empty(), include);
+                assert!(!iter.set_emptyset(!include));
+                assert_eq!(iter.len(), 6 + include as usize);
+                iter.reset();
+                assert_eq!(iter.len(), 7 + include as usize);
+            }
+        }
+
+        let mut iter = SubsetGenerator::new(&data, false).iter();
+        iter.next();
+        assert!(!iter.set_emptyset(true));
+        assert_eq!(iter.count(), 6);
+
+        let generator = SubsetGenerator::new(&data, false).with_required(&[0]);
+        let mut iter = generator.iter();
+        assert!(!iter.set_emptyset(false));
+        assert_eq!(iter.next(), Some(vec![&1]));
+    }
+
+    #[test]
+    fn next_with_popcount_matches_combinations() {
+        for &len in &[8, 70] {
+            let data: Vec<usize> = (0..len).collect();
+            let generator = SubsetGenerator::new(&data, false);
+            for &k in &[1, 2, 3, len - 1, len] {
+                let mut iter = generator.iter();
+                let expected = generator.combinations(k).take(200);
+                for subset in expected {
+                    assert_eq!(iter.next_with_popcount(k), Some(subset));
+                }
+            }
+        }
+
+        let data: Vec<usize> = (0..8).collect();
+        let generator = SubsetGenerator::new(&data, true);
+        for k in 0..=8 {
+            let mut iter = generator.iter();
+            let selected: Vec<_> = core::iter::from_fn(|| iter.next_with_popcount(k)).collect();
+            assert_eq!(selected, generator.combinations(k).collect::<Vec<_>>());
+            assert_eq!(iter.next(), None);
+        }
+
+        let mut iter = generator.split(2).remove(0);
+        assert_eq!(iter.next_with_popcount(4), Some(vec![&0, &1, &2, &3]));
+        assert_eq!(iter.next_with_popcount(8), None);
+        assert_eq!(iter.len(), 0);
+    }
+
+    #[test]
+    fn peek_next_mask_agrees_with_next() {
+        for &len in &[5, 70] {
+            let data: Vec<usize> = (0..len).collect();
+            for &with_emptyset in &[false, true] {
+                let generator = SubsetGenerator::new(&data, with_emptyset).with_forbidden(&[1]);
+                let mut iter = if len < 64 {
+                    generator.split(3).remove(0)
+                } else {
+                    generator.iter()
+                };
+                for _ in 0..40 {
+                    let peeked = iter.peek_next_mask();
+                    assert_eq!(iter.peek_next_mask(), peeked);
+                    match iter.next() {
+                        Some(_) => assert_eq!(peeked, Some(iter.current_mask())),
+                        None => {
+                            assert_eq!(peeked, None);
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cursor_accessors_match_subset() {
+        for &len in &[6, 70] {
+            let data: Vec<usize> = (0..len).collect();
+            let generator = SubsetGenerator::new(&data, false);
+            let constrained = generator.clone().with_required(&[2]).with_forbidden(&[0]);
+            for sg in &[generator, constrained] {
+                let mut iter = sg.iter();
+                while let Some(subset) = iter.next() {
+                    let indices: Vec<usize> = subset.iter().map(|&&x| x).collect();
+                    assert_eq!(iter.selected_count(), indices.len());
+                    assert!((0..len).all(|i| iter.contains_index(i) == indices.contains(&i)));
+                    if iter.current_index() >= 40 {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn next_past_exhaustion() {
+        for &n in &[0, 3, 64, 70] {
+            let data: Vec<u8> = vec![0; n];
+            let generator = SubsetGenerator::new(&data, true);
+            let mut iter = generator.iter();
+            if n < 64 {
+                assert_eq!(iter.by_ref().count(), 1 << n);
+            } else {
+                // Jump straight to the last subset, which selects everything.
+                assert_eq!(iter.seek_mask(Mask::ones(n)).map(|s| s.len()), Some(n));
+            }
+            for _ in 0..5 {
+                assert_eq!(iter.next(), None);
+                assert_eq!(iter.next_back(), None);
+                assert_eq!(iter.len(), 0);
+            }
+            assert!(iter.current_mask().all());
+            iter.reset();
+            assert_eq!(iter.next(), Some(vec![]));
+        }
+    }
+
+    #[test]
+    fn collect_all_exact_capacity() {
+        let data: Vec<usize> = (0..7).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let constrained = generator.clone().with_required(&[3]).with_forbidden(&[0]);
+            for sg in &[generator, constrained] {
+                let subsets = sg.collect_all();
+                assert_eq!(subsets, sg.iter().collect::<Vec<_>>());
+                assert_eq!(subsets.capacity(), subsets.len());
+            }
+        }
+        let empty: Vec<usize> = vec![];
+        assert!(SubsetGenerator::new(&empty, false).collect_all().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn collect_all_too_many_subsets() {
+        let data: Vec<usize> = (0..70).collect();
+        SubsetGenerator::new(&data, false).collect_all();
+    }
+
+    #[test]
+    fn zero_length_cursor() {
+        let empty: Vec<u8> = vec![];
+        let data = vec![1, 2];
+        let required = SubsetGenerator::new(&data, false).with_required(&[0, 1]);
+        let cases = [
+            (SubsetGenerator::new(&empty, false), vec![]),
+            (SubsetGenerator::new(&empty, true), vec![vec![]]),
+            (required, vec![vec![&1, &2]]),
+        ];
+        for (generator, expected) in &cases {
+            let mut iter = generator.iter();
+            assert_eq!(iter.len(), expected.len());
+            assert_eq!(&iter.by_ref().collect::<Vec<_>>(), expected);
+            for _ in 0..3 {
+                assert_eq!(iter.next(), None);
+                assert_eq!(iter.next_back(), None);
+            }
+            assert_eq!(&generator.iter().rev().collect::<Vec<_>>(), expected);
+            assert_eq!(generator.iter().advance_by(3), expected.len());
+            let popcount: Vec<_> = generator.iter().next_with_popcount(0).into_iter().collect();
+            assert_eq!(&popcount, expected);
+            assert_eq!(generator.iter().seek(0).is_some(), !expected.is_empty());
+        }
+    }
+
+    #[test]
+    fn from_array_sizes() {
+        let empty: [u8; 0] = [];
+        assert_eq!(SubsetGenerator::from_array(&empty, false).iter().count(), 0);
+        assert_eq!(
+            SubsetGenerator::from_array(&empty, true)
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![Vec::<&u8>::new()]
+        );
+
+        let single = [7];
+        let subsets: Vec<_> = SubsetGenerator::from_array(&single, true).iter().collect();
+        assert_eq!(subsets, vec![vec![], vec![&7]]);
+
+        let small = [1, 2, 3, 4, 5];
+        let generator = SubsetGenerator::from_array(&small, false);
+        assert!(generator
+            .iter()
+            .eq(SubsetGenerator::new(&small[..], false).iter()));
+
+        let full: [u64; 64] = core::array::from_fn(|i| i as u64);
+        let generator = SubsetGenerator::from_array(&full, false);
+        assert_eq!(generator.iter().next_back().map(|s| s.len()), Some(64));
+        assert_eq!(generator.iter().seek(u64::MAX).map(|s| s.len()), Some(64));
+        let mut last = generator.split(3).pop().unwrap();
+        assert_eq!(last.next_back().map(|s| s.len()), Some(64));
+    }
+
+    #[test]
+    fn try_new_limits() {
+        let data = vec![0u8; 64];
+        assert!(SubsetGenerator::try_new(&data, true).is_ok());
+        let data = vec![0u8; 65];
+        let result = SubsetGenerator::try_new(&data, true).map(|_| ());
+        if cfg!(feature = "big") {
+            assert_eq!(result, Ok(()));
+        } else {
+            let error = SubsetError::TooLarge { n: 65, max: 64 };
+            assert_eq!(result, Err(error));
+            assert_eq!(
+                error.to_string(),
+                "a dataset of 65 elements exceeds the supported maximum of 64"
+            );
+        }
+    }
+
+    fn assert_send<S: Send>() {}
+    fn assert_sync<S: Sync>() {}
+
+    fn assert_thread_safe<'a, T: Sync + 'a>() {
+        assert_send::<SubsetIter<'a, T>>();
+        assert_sync::<SubsetIter<'a, T>>();
+        assert_send::<SubsetGenerator<'a, T>>();
+        assert_sync::<SubsetGenerator<'a, T>>();
+    }
+
+    fn assert_fused<I: FusedIterator>() {}
+
+    #[test]
+    fn fused_iterators() {
+        assert_fused::<SubsetIter<'static, i32>>();
+        assert_fused::<ColexSubsetIter<'static, i32>>();
+        assert_fused::<ClonedSubsetIter<'static, i32>>();
+        assert_fused::<ComplementIter<'static, i32>>();
+        assert_fused::<BipartitionIter<'static, i32>>();
+        assert_fused::<IndexSubsetIter<'static, i32>>();
+        assert_fused::<IndexedSubsetIter<'static, i32>>();
+        assert_fused::<MaskedSubsetIter<'static, i32>>();
+        assert_fused::<BitVecSubsetIter<'static, i32>>();
+        assert_fused::<WithIndices<'static, i32>>();
+        assert_fused::<ChunkedSubsetIter<'static, i32>>();
+        assert_fused::<GraySubsetIter<'static, i32>>();
+        assert_fused::<LexSubsetIter<'static, i32>>();
+        assert_fused::<IndependentSubsetIter<'static, i32>>();
+        assert_fused::<PopcountOrderedIter<'static, i32>>();
+        assert_fused::<ShuffledSubsetIter<'static, i32>>();
+        assert_fused::<LayerSplitIter<'static, i32>>();
+        assert_fused::<LazySubsetIter<'static, i32>>();
+        assert_fused::<SubsetElements<'static, i32>>();
+        assert_fused::<OwnedClonedIter<i32>>();
+        assert_fused::<SetSubsetIter<'static, 'static, i32>>();
+        assert_fused::<MaskGenerator>();
+        assert_fused::<MaskIndices>();
+
+        let data: Vec<i32> = (0..5).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset).with_forbidden(&[3]);
+            let mut iters: Vec<Box<dyn Iterator<Item = Vec<&i32>>>> = vec![
+                Box::new(generator.iter()),
+                Box::new(generator.iter().rev()),
+                Box::new(generator.gray_iter()),
+                Box::new(generator.lex_iter()),
+                Box::new(generator.by_popcount()),
+                Box::new(generator.shuffled_iter(3)),
+                Box::new(generator.independent_iter(&[(0, 1)])),
+                Box::new(generator.filtered(|s| s.len() == 2)),
+            ];
+            for iter in iters.iter_mut() {
+                while iter.next().is_some() {}
+                for _ in 0..4 {
+                    assert_eq!(iter.next(), None);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn send_and_sync() {
+        assert_thread_safe::<i32>();
+        assert_thread_safe::<std::sync::Mutex<String>>();
+    }
+
+    #[test]
+    fn size_hint_overflow() {
+        let data = vec![0; usize::BITS as usize + 1];
+        let large_generator = SubsetGenerator::new(&data, false);
+        assert_eq!(large_generator.iter().size_hint(), (usize::MAX, None));
     }
 }
@@ -0,0 +1,509 @@
+use alloc::vec::Vec;
+use bit_vec::BitVec;
+use core::convert::TryFrom;
+use core::fmt;
+
+/// The cursor of a [`SubsetIter`](crate::SubsetIter). Datasets of at most 64
+/// elements use a plain `u64`, so that the common operations are single
+/// arithmetic instructions; larger datasets fall back to a BitVec. Bit `i`
+/// corresponds to the `i`-th element of the dataset in both representations.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Mask {
+    Small { bits: u64, len: usize },
+    Large(BitVec),
+}
+
+impl Mask {
+    /// Returns the mask of length `len` whose integer value is `index`.
+    pub(crate) fn from_index(index: u64, len: usize) -> Mask {
+        if len <= 64 {
+            Mask::Small { bits: index, len }
+        } else {
+            Mask::Large(crate::rank::unrank(index, len))
+        }
+    }
+
+    /// Returns the mask of length `len` with all bits set.
+    pub(crate) fn ones(len: usize) -> Mask {
+        if len <= 64 {
+            Mask::Small {
+                bits: all_ones(len),
+                len,
+            }
+        } else {
+            Mask::Large(BitVec::from_elem(len, true))
+        }
+    }
+
+    /// Sets every bit to `value`, reusing the storage of the mask.
+    pub(crate) fn fill(&mut self, value: bool) {
+        match self {
+            Mask::Small { bits, len } => *bits = if value { all_ones(*len) } else { 0 },
+            Mask::Large(set) => set.fill(value),
+        }
+    }
+
+    /// Converts a BitVec into a mask, picking the representation by length.
+    pub(crate) fn from_bitvec(set: BitVec) -> Mask {
+        if set.len() <= 64 {
+            Mask::Small {
+                bits: crate::rank::rank(&set),
+                len: set.len(),
+            }
+        } else {
+            Mask::Large(set)
+        }
+    }
+
+    /// Converts the mask into a BitVec of the same length.
+    pub(crate) fn to_bitvec(&self) -> BitVec {
+        match self {
+            Mask::Small { bits, len } => crate::rank::unrank(*bits, *len),
+            Mask::Large(set) => set.clone(),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            Mask::Small { len, .. } => *len,
+            Mask::Large(set) => set.len(),
+        }
+    }
+
+    pub(crate) fn get(&self, i: usize) -> bool {
+        match self {
+            Mask::Small { bits, .. } => (bits >> i) & 1 == 1,
+            Mask::Large(set) => set[i],
+        }
+    }
+
+    /// Returns the integer value of the mask.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a bit at position 64 or higher is set.
+    pub(crate) fn index(&self) -> u64 {
+        match self {
+            Mask::Small { bits, .. } => *bits,
+            Mask::Large(set) => crate::rank::rank(set),
+        }
+    }
+
+    /// Adds 1 to the mask. Returns false, leaving the mask unchanged, if all
+    /// the bits were set. For a BitVec the increment is a single pass: the
+    /// carry clears the trailing set bits until it reaches a clear bit, and if
+    /// it propagates past the final bit then every bit was set. In that case
+    /// the bits are restored so the mask does not wrap around.
+    pub(crate) fn increment(&mut self) -> bool {
+        match self {
+            Mask::Small { bits, len } => {
+                if *bits == all_ones(*len) {
+                    return false;
+                }
+                *bits += 1;
+                true
+            }
+            Mask::Large(set) => {
+                for i in 0..set.len() {
+                    if set[i] {
+                        set.set(i, false);
+                    } else {
+                        set.set(i, true);
+                        return true;
+                    }
+                }
+
+                set.fill(true);
+                false
+            }
+        }
+    }
+
+    /// Subtracts 1 from the mask. Returns false, leaving the mask unchanged,
+    /// if no bits were set.
+    pub(crate) fn decrement(&mut self) -> bool {
+        match self {
+            Mask::Small { bits, .. } => {
+                if *bits == 0 {
+                    return false;
+                }
+                *bits -= 1;
+                true
+            }
+            Mask::Large(set) => {
+                if set.none() {
+                    return false;
+                }
+                for i in 0..set.len() {
+                    if set[i] {
+                        set.set(i, false);
+                        break;
+                    } else {
+                        set.set(i, true);
+                    }
+                }
+                true
+            }
+        }
+    }
+
+    /// Adds `amount` to the mask. The sum must not exceed the value with all
+    /// bits set.
+    pub(crate) fn add(&mut self, amount: usize) {
+        match self {
+            Mask::Small { bits, .. } => *bits += amount as u64,
+            Mask::Large(set) => {
+                let mut carry = false;
+                for i in 0..set.len() {
+                    let bit = i < usize::BITS as usize && (amount >> i) & 1 == 1;
+                    if !bit && !carry && i >= usize::BITS as usize {
+                        break;
+                    }
+                    let old = set[i];
+                    set.set(i, old ^ bit ^ carry);
+                    carry = (old & bit) | (carry & (old ^ bit));
+                }
+            }
+        }
+    }
+
+    /// Returns true if the integer value of the mask is smaller than the one
+    /// of `other`. Both masks must have the same length.
+    pub(crate) fn less_than(&self, other: &Mask) -> bool {
+        match (self, other) {
+            (Mask::Small { bits: a, .. }, Mask::Small { bits: b, .. }) => a < b,
+            (Mask::Large(a), Mask::Large(b)) => {
+                for i in (0..a.len()).rev() {
+                    if a[i] != b[i] {
+                        return b[i];
+                    }
+                }
+                false
+            }
+            _ => unreachable!("masks of different lengths"),
+        }
+    }
+
+    /// Returns `higher - self` as integers, or `None` if the difference does
+    /// not fit in a `usize`. Both masks must have the same length, and the
+    /// value of `higher` must be at least the value of `self`.
+    pub(crate) fn distance(&self, higher: &Mask) -> Option<usize> {
+        match (self, higher) {
+            (Mask::Small { bits: low, .. }, Mask::Small { bits: high, .. }) => {
+                usize::try_from(high - low).ok()
+            }
+            (Mask::Large(low), Mask::Large(high)) => {
+                let mut distance: usize = 0;
+                let mut borrow = false;
+                for i in 0..low.len() {
+                    let (h, l) = (high[i], low[i]);
+                    let bit = h ^ l ^ borrow;
+                    borrow = (!h & (l | borrow)) | (l & borrow);
+                    if bit {
+                        if i >= usize::BITS as usize {
+                            return None;
+                        }
+                        distance |= 1 << i;
+                    }
+                }
+                Some(distance)
+            }
+            _ => unreachable!("masks of different lengths"),
+        }
+    }
+
+    /// Returns the integer value of the mask divided by `2^len`, which lies in
+    /// `[0, 1)`. Unlike the value itself, the fraction never overflows.
+    pub(crate) fn fraction(&self) -> f64 {
+        match self {
+            Mask::Small { bits, len } => *bits as f64 * exp2(-(*len as i32)),
+            Mask::Large(set) => {
+                let len = set.len() as i32;
+                (0..set.len())
+                    .filter(|&i| set[i])
+                    .map(|i| exp2(i as i32 - len))
+                    .sum()
+            }
+        }
+    }
+
+    /// Returns the number of set bits.
+    pub(crate) fn count_ones(&self) -> usize {
+        match self {
+            Mask::Small { bits, .. } => bits.count_ones() as usize,
+            Mask::Large(set) => set.iter().filter(|&bit| bit).count(),
+        }
+    }
+
+    /// Sets the lowest `count` clear bits. There must be at least `count`
+    /// clear bits.
+    pub(crate) fn fill_lowest_zeros(&mut self, count: usize) {
+        match self {
+            Mask::Small { bits, .. } => {
+                for _ in 0..count {
+                    *bits |= *bits + 1;
+                }
+            }
+            Mask::Large(set) => {
+                let mut count = count;
+                for i in 0..set.len() {
+                    if count == 0 {
+                        break;
+                    }
+                    if !set[i] {
+                        set.set(i, true);
+                        count -= 1;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Moves to the smallest mask above the current one with exactly `count`
+    /// set bits. Returns false, leaving the mask unchanged, if there is none.
+    /// Runs of set bits that are too long are carried past in one step each,
+    /// and the remaining bits are then filled in from the bottom, so only the
+    /// masks on the way that could have the right number of bits are visited.
+    pub(crate) fn next_with_ones(&mut self, count: usize) -> bool {
+        if count > self.len() {
+            return false;
+        }
+        let mut next = self.clone();
+        if !next.increment() {
+            return false;
+        }
+        loop {
+            let ones = next.count_ones();
+            if ones <= count {
+                next.fill_lowest_zeros(count - ones);
+                *self = next;
+                return true;
+            }
+            if !next.carry_lowest_run() {
+                return false;
+            }
+        }
+    }
+
+    /// Clears the lowest run of set bits and sets the bit above it, which adds
+    /// the value of the lowest set bit. Returns false if the run ends at the
+    /// final bit, leaving the mask in an unspecified state.
+    fn carry_lowest_run(&mut self) -> bool {
+        match self {
+            Mask::Small { bits, len } => {
+                let next = bits.wrapping_add(*bits & bits.wrapping_neg());
+                if next == 0 || next > all_ones(*len) {
+                    return false;
+                }
+                *bits = next;
+                true
+            }
+            Mask::Large(set) => {
+                let mut i = match (0..set.len()).find(|&i| set[i]) {
+                    Some(low) => low,
+                    None => return false,
+                };
+                while i < set.len() && set[i] {
+                    set.set(i, false);
+                    i += 1;
+                }
+                if i == set.len() {
+                    return false;
+                }
+                set.set(i, true);
+                true
+            }
+        }
+    }
+
+    /// Returns the position of the lowest set bit, or `None` if no bits are
+    /// set.
+    pub(crate) fn lowest_set(&self) -> Option<usize> {
+        match self {
+            Mask::Small { bits: 0, .. } => None,
+            Mask::Small { bits, .. } => Some(bits.trailing_zeros() as usize),
+            Mask::Large(set) => (0..set.len()).find(|&i| set[i]),
+        }
+    }
+
+    /// Calls `f` with the position of every set bit, in increasing order.
+    pub(crate) fn for_each_one<F: FnMut(usize)>(&self, mut f: F) {
+        match self {
+            Mask::Small { bits, .. } => {
+                let mut bits = *bits;
+                while bits != 0 {
+                    f(bits.trailing_zeros() as usize);
+                    bits &= bits - 1;
+                }
+            }
+            Mask::Large(set) => {
+                for i in 0..set.len() {
+                    if set[i] {
+                        f(i);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Appends references to the elements of `data` whose bits are set to
+    /// `buf`.
+    pub(crate) fn collect_into<'a, T>(&self, data: &'a [T], buf: &mut Vec<&'a T>) {
+        match self {
+            Mask::Small { bits, .. } => {
+                let mut bits = *bits;
+                while bits != 0 {
+                    buf.push(&data[bits.trailing_zeros() as usize]);
+                    bits &= bits - 1;
+                }
+            }
+            Mask::Large(set) => crate::subset_into(data, set, buf),
+        }
+    }
+}
+
+/// Formats the mask as a binary number of exactly `len` digits, so the bit of
+/// the first element is the rightmost digit.
+impl fmt::Binary for Mask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mask::Small { len: 0, .. } => Ok(()),
+            Mask::Small { bits, len } => write!(f, "{:0width$b}", bits, width = len),
+            Mask::Large(set) => {
+                for i in (0..set.len()).rev() {
+                    f.write_str(if set[i] { "1" } else { "0" })?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Adds 1 to the front cursor `set`, as long as it stays at or below the back
+/// cursor `back`. Returns false, and marks the cursors as exhausted, once all
+/// the non-empty masks have been reported. A cursor of length 0 has no
+/// non-empty masks at all.
+pub(crate) fn step_forward(set: &mut Mask, back: &Mask, exhausted: &mut bool) -> bool {
+    if set.len() == 0 || *exhausted || !(set.less_than(back) && set.increment()) {
+        *exhausted = true;
+        return false;
+    }
+    true
+}
+
+/// Subtracts 1 from the back cursor `back`, which is the highest mask that has
+/// not been reported yet. Returns false, and marks the cursors as exhausted,
+/// once the back cursor met the front cursor `set`.
+pub(crate) fn step_backward(set: &Mask, back: &mut Mask, exhausted: &mut bool) -> bool {
+    if *exhausted || !(set.less_than(back) && back.decrement()) {
+        *exhausted = true;
+        return false;
+    }
+    true
+}
+
+/// Returns `2^exponent`, as `f64::powi` is not available without `std`. Powers
+/// below the smallest normal number are rounded down to 0.
+pub(crate) fn exp2(exponent: i32) -> f64 {
+    if exponent < -1022 {
+        0.0
+    } else if exponent > 1023 {
+        f64::INFINITY
+    } else {
+        f64::from_bits(((exponent + 1023) as u64) << 52)
+    }
+}
+
+/// Returns the `u64` with the lowest `len` bits set, for `len <= 64`.
+fn all_ones(len: usize) -> u64 {
+    if len == 64 {
+        u64::MAX
+    } else {
+        (1 << len) - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn representations_agree() {
+        for &len in &[0, 1, 5, 64] {
+            let mut small = Mask::from_index(0, len);
+            let mut large = Mask::Large(BitVec::from_elem(len, false));
+            let ones = Mask::ones(len);
+            for _ in 0..40 {
+                assert_eq!(small.to_bitvec(), large.to_bitvec());
+                assert_eq!(small.lowest_set(), large.lowest_set());
+                assert_eq!(small.less_than(&ones), !small.eq(&ones));
+                assert_eq!(small.increment(), large.increment());
+            }
+            while small.decrement() {
+                assert!(large.decrement());
+                assert_eq!(small.to_bitvec(), large.to_bitvec());
+            }
+            assert!(!large.decrement());
+        }
+    }
+
+    #[test]
+    fn next_with_ones_agrees() {
+        for &len in &[0, 6, 64] {
+            for count in 0..=len.min(8) {
+                let mut small = Mask::from_index(0, len);
+                let mut large = Mask::Large(BitVec::from_elem(len, false));
+                for _ in 0..50 {
+                    let moved = small.next_with_ones(count);
+                    assert_eq!(moved, large.next_with_ones(count));
+                    assert_eq!(small.to_bitvec(), large.to_bitvec());
+                    if !moved {
+                        break;
+                    }
+                    assert_eq!(small.count_ones(), count);
+                }
+            }
+        }
+        let mut mask = Mask::from_index(0b1110, 4);
+        assert!(!mask.next_with_ones(3));
+        assert_eq!(mask.index(), 0b1110);
+    }
+
+    #[test]
+    fn binary_format() {
+        assert_eq!(format!("{:b}", Mask::from_index(6, 5)), "00110");
+        assert_eq!(format!("{:b}", Mask::from_index(0, 0)), "");
+        let large = Mask::Large(BitVec::from_fn(66, |i| i == 0 || i == 65));
+        assert_eq!(format!("{:b}", large), format!("1{}1", "0".repeat(64)));
+    }
+
+    #[test]
+    fn powers_of_two() {
+        assert_eq!(exp2(0), 1.0);
+        assert_eq!(exp2(10), 1024.0);
+        assert_eq!(exp2(-3), 0.125);
+        assert_eq!(exp2(-2000), 0.0);
+        assert_eq!(Mask::from_index(6, 4).fraction(), 0.375);
+    }
+
+    #[test]
+    fn large_add() {
+        let mut mask = Mask::from_bitvec(BitVec::from_fn(70, |i| i < 66));
+        mask.add(1);
+        let expected = BitVec::from_fn(70, |i| i == 66);
+        assert_eq!(mask, Mask::Large(expected));
+
+        let mut mask = Mask::from_index(0, 70);
+        mask.add(12345);
+        assert_eq!(mask.index(), 12345);
+    }
+
+    #[test]
+    fn large_distance() {
+        let low = Mask::Large(BitVec::from_fn(70, |i| i == 3));
+        let high = Mask::Large(BitVec::from_fn(70, |i| i == 1 || i == 4));
+        assert_eq!(low.distance(&high), Some(10));
+        let top = Mask::ones(70);
+        assert_eq!(low.distance(&top), None);
+    }
+}
@@ -0,0 +1,303 @@
+use crate::mask::{step_backward, step_forward, Mask};
+use bit_vec::BitVec;
+use core::iter::FusedIterator;
+
+/// Iterator over the masks of all the subsets of `0..n`, without a dataset.
+///
+/// The masks are reported in the same order as the subsets of a
+/// [`SubsetGenerator`](crate::SubsetGenerator) over `n` elements, i.e. by
+/// increasing integer value, and bit `i` of a mask is set if and only if the
+/// `i`-th element would be included. In fact, the subset iterators step their
+/// cursors with the same logic, so this is the counting at the core of the
+/// crate on its own, for combinatorial experiments where the elements do not
+/// matter.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::MaskGenerator;
+///
+/// let masks: Vec<u64> = MaskGenerator::new(2, true).indices().collect();
+/// assert_eq!(masks, vec![0b00, 0b01, 0b10, 0b11]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MaskGenerator {
+    set: Mask,
+    back: Mask,
+    with_emptyset: bool,
+    exhausted: bool,
+}
+
+impl MaskGenerator {
+    /// Constructs an iterator over the masks of the subsets of `0..n`. The
+    /// empty mask is reported first if (and only if) `with_emptyset` is true,
+    /// so there are `2^n` masks with it and `2^n - 1` without.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::MaskGenerator;
+    ///
+    /// let mut masks = MaskGenerator::new(3, false);
+    /// assert_eq!(masks.len(), 7);
+    ///
+    /// let first = masks.next().unwrap();
+    /// assert_eq!(first.len(), 3);
+    /// assert!(first[0] && !first[1] && !first[2]);
+    /// ```
+    pub fn new(n: usize, with_emptyset: bool) -> MaskGenerator {
+        MaskGenerator {
+            set: Mask::from_index(0, n),
+            back: Mask::ones(n),
+            with_emptyset,
+            exhausted: false,
+        }
+    }
+
+    /// Converts the iterator into one over the integer values of the masks,
+    /// which avoids allocating a BitVec for every mask. The iterator continues
+    /// from the current position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is larger than 64, as the masks would not fit in a `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::MaskGenerator;
+    ///
+    /// let singletons = MaskGenerator::new(4, false)
+    ///     .indices()
+    ///     .filter(|mask| mask.count_ones() == 1);
+    /// assert_eq!(singletons.collect::<Vec<_>>(), vec![1, 2, 4, 8]);
+    /// ```
+    pub fn indices(self) -> MaskIndices {
+        assert!(
+            self.set.len() <= 64,
+            "indices supports masks of at most 64 bits"
+        );
+        MaskIndices { masks: self }
+    }
+
+    /// Steps the front cursor to the next mask. Returns false if there is
+    /// none, in which case the pending empty mask has been taken as well.
+    fn step(&mut self) -> bool {
+        if self.with_emptyset {
+            self.with_emptyset = false;
+            return true;
+        }
+        step_forward(&mut self.set, &self.back, &mut self.exhausted)
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        self.set
+            .distance(&self.back)?
+            .checked_add(self.with_emptyset as usize)
+    }
+}
+
+impl Iterator for MaskGenerator {
+    type Item = BitVec;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let base = self.with_emptyset;
+        if !self.step() {
+            return None;
+        }
+        if base {
+            Some(BitVec::from_elem(self.set.len(), false))
+        } else {
+            Some(self.set.to_bitvec())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining() {
+            Some(remaining) => (remaining, Some(remaining)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+impl DoubleEndedIterator for MaskGenerator {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if !self.exhausted && self.set.less_than(&self.back) {
+            let mask = self.back.to_bitvec();
+            step_backward(&self.set, &mut self.back, &mut self.exhausted);
+            return Some(mask);
+        }
+        self.exhausted = true;
+        if self.with_emptyset {
+            self.with_emptyset = false;
+            return Some(BitVec::from_elem(self.set.len(), false));
+        }
+        None
+    }
+}
+
+/// The number of masks is known up front: `2^n` with the empty mask and
+/// `2^n - 1` without it.
+///
+/// # Panics
+///
+/// `len` panics if the number of remaining masks does not fit in a `usize`,
+/// which can only happen for `n >= usize::BITS`.
+impl ExactSizeIterator for MaskGenerator {
+    fn len(&self) -> usize {
+        self.remaining()
+            .expect("the number of remaining masks does not fit in a usize")
+    }
+}
+
+impl FusedIterator for MaskGenerator {}
+
+/// Iterator over the integer values of the masks of all the subsets of `0..n`,
+/// created by [`MaskGenerator::indices`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::MaskGenerator;
+///
+/// let total: u64 = MaskGenerator::new(3, false).indices().sum();
+/// assert_eq!(total, 28);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MaskIndices {
+    masks: MaskGenerator,
+}
+
+impl Iterator for MaskIndices {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let base = self.masks.with_emptyset;
+        if !self.masks.step() {
+            return None;
+        }
+        if base {
+            Some(0)
+        } else {
+            Some(self.masks.set.index())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.masks.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for MaskIndices {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let masks = &mut self.masks;
+        if !masks.exhausted && masks.set.less_than(&masks.back) {
+            let index = masks.back.index();
+            step_backward(&masks.set, &mut masks.back, &mut masks.exhausted);
+            return Some(index);
+        }
+        masks.exhausted = true;
+        if masks.with_emptyset {
+            masks.with_emptyset = false;
+            return Some(0);
+        }
+        None
+    }
+}
+
+/// # Panics
+///
+/// `len` panics if the number of remaining masks does not fit in a `usize`,
+/// like for [`MaskGenerator`], which can only happen for `n >= usize::BITS`.
+impl ExactSizeIterator for MaskIndices {
+    fn len(&self) -> usize {
+        self.masks.len()
+    }
+}
+
+impl FusedIterator for MaskIndices {}
+
+#[cfg(test)]
+mod tests {
+    use super::MaskGenerator;
+    use crate::SubsetGenerator;
+
+    #[test]
+    fn masks_count_up() {
+        for n in 0..=10 {
+            for &with_emptyset in &[false, true] {
+                let expected: Vec<u64> = (!with_emptyset as u64..1 << n).collect();
+                let masks = MaskGenerator::new(n, with_emptyset);
+                assert_eq!(masks.len(), expected.len());
+                assert_eq!(masks.clone().indices().collect::<Vec<_>>(), expected);
+
+                let bitvecs: Vec<_> = masks.collect();
+                assert!(bitvecs.iter().all(|mask| mask.len() == n));
+                let values: Vec<u64> = bitvecs.iter().map(crate::rank::rank).collect();
+                assert_eq!(values, expected);
+
+                let mut backward: Vec<_> = MaskGenerator::new(n, with_emptyset)
+                    .indices()
+                    .rev()
+                    .collect();
+                backward.reverse();
+                assert_eq!(backward, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn masks_match_subsets() {
+        for &n in &[1, 5, 9] {
+            let data: Vec<usize> = (0..n).collect();
+            for &with_emptyset in &[false, true] {
+                let generator = SubsetGenerator::new(&data, with_emptyset);
+                let masks = MaskGenerator::new(n, with_emptyset);
+                for (mask, subset) in masks.zip(generator.iter()) {
+                    let selected: Vec<&usize> = data.iter().filter(|&&i| mask[i]).collect();
+                    assert_eq!(selected, subset);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn masks_meet_in_the_middle() {
+        let mut masks = MaskGenerator::new(3, true).indices();
+        assert_eq!(masks.next(), Some(0));
+        assert_eq!(masks.next_back(), Some(7));
+        assert_eq!(masks.next(), Some(1));
+        assert_eq!(masks.next_back(), Some(6));
+        assert_eq!(masks.len(), 4);
+        assert_eq!(masks.by_ref().collect::<Vec<_>>(), vec![2, 3, 4, 5]);
+        assert_eq!(masks.next(), None);
+        assert_eq!(masks.next_back(), None);
+
+        let mut masks = MaskGenerator::new(0, true);
+        assert_eq!(masks.next_back().map(|mask| mask.len()), Some(0));
+        assert_eq!(masks.next(), None);
+    }
+
+    #[test]
+    fn large_masks() {
+        let mut masks = MaskGenerator::new(100, false);
+        let first = masks.next().unwrap();
+        assert_eq!(first.len(), 100);
+        assert!(first[0] && first.iter().filter(|&bit| bit).count() == 1);
+        let last = masks.next_back().unwrap();
+        assert!(last.all());
+        assert_eq!(masks.size_hint(), (usize::MAX, None));
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit in a usize")]
+    fn large_masks_len() {
+        MaskGenerator::new(usize::BITS as usize, true).len();
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit in a usize")]
+    fn full_width_indices_len() {
+        MaskGenerator::new(64, true).indices().len();
+    }
+}
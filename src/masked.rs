@@ -0,0 +1,222 @@
+use crate::mask::Mask;
+use crate::{SubsetGenerator, SubsetIter};
+use alloc::vec::Vec;
+use bit_vec::BitVec;
+use core::iter::FusedIterator;
+
+/// Iterator over all the subsets together with their bitmasks, created by
+/// [`SubsetGenerator::masked_iter`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2];
+/// let sg = SubsetGenerator::new(&data, true);
+///
+/// let masks: Vec<u64> = sg.masked_iter().map(|(mask, _)| mask).collect();
+/// assert_eq!(masks, vec![0, 1, 2, 3]);
+/// ```
+pub struct MaskedSubsetIter<'a, T> {
+    iter: SubsetIter<'a, T>,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over all the subsets, where each subset is paired
+    /// with its bitmask: bit `i` of the mask is set if and only if the `i`-th
+    /// element of the dataset is included. The mask is a stable integer key
+    /// for the subset, which is convenient for subset DP and memoization. The
+    /// subsets are reported in the same order as by `iter`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dataset has more than 64 elements, as the mask would not
+    /// fit in a `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.masked_iter();
+    ///
+    /// assert_eq!(iter.next(), Some((1, vec![&1])));
+    /// assert_eq!(iter.next(), Some((2, vec![&2])));
+    /// assert_eq!(iter.next(), Some((3, vec![&1, &2])));
+    /// ```
+    pub fn masked_iter(&self) -> MaskedSubsetIter<'a, T> {
+        assert!(
+            self.data.len() <= 64,
+            "masked_iter supports datasets of at most 64 elements"
+        );
+        MaskedSubsetIter { iter: self.iter() }
+    }
+}
+
+impl<'a, T> Iterator for MaskedSubsetIter<'a, T> {
+    type Item = (u64, Vec<&'a T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The pending base subset is reported before the cursor moves, and
+        // corresponds to the cursor value 0.
+        let base = self.iter.with_emptyset;
+        let subset = self.iter.next()?;
+        let mask = match (&self.iter.constraints, base) {
+            (Some(constraints), true) => {
+                constraints.index(&Mask::from_index(0, self.iter.set.len()))
+            }
+            (Some(constraints), false) => constraints.index(&self.iter.set),
+            (None, true) => 0,
+            (None, false) => self.iter.set.index(),
+        };
+        Some((mask, subset))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for MaskedSubsetIter<'a, T> {}
+
+impl<'a, T> FusedIterator for MaskedSubsetIter<'a, T> {}
+
+/// Iterator over all the subsets together with their bitmasks as BitVecs,
+/// created by [`SubsetGenerator::bitvec_iter`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// let (mask, subset) = sg.bitvec_iter().last().unwrap();
+/// assert!(mask.all());
+/// assert_eq!(subset, vec![&1, &2]);
+/// ```
+pub struct BitVecSubsetIter<'a, T> {
+    iter: SubsetIter<'a, T>,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over all the subsets, where each subset is paired
+    /// with its bitmask as a BitVec of length `n`: bit `i` is set if and only
+    /// if the `i`-th element of the dataset is included. This is the
+    /// counterpart of [`masked_iter`](Self::masked_iter) for datasets of any
+    /// size, as the mask is not limited to 64 bits. The price is that every
+    /// item allocates a BitVec besides the subset itself. The subsets are
+    /// reported in the same order as by `iter`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data: Vec<u32> = (0..100).collect();
+    /// let sg = SubsetGenerator::new(&data, false);
+    ///
+    /// let mut memo = HashMap::new();
+    /// for (mask, subset) in sg.bitvec_iter().take(10) {
+    ///     memo.insert(mask, subset.len());
+    /// }
+    /// assert_eq!(memo.len(), 10);
+    /// ```
+    pub fn bitvec_iter(&self) -> BitVecSubsetIter<'a, T> {
+        BitVecSubsetIter { iter: self.iter() }
+    }
+}
+
+impl<'a, T> Iterator for BitVecSubsetIter<'a, T> {
+    type Item = (BitVec, Vec<&'a T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The pending base subset is reported before the cursor moves.
+        let base = self.iter.with_emptyset;
+        let subset = self.iter.next()?;
+        let mask = if base { None } else { Some(&self.iter.set) };
+        let bits = match (&self.iter.constraints, mask) {
+            (Some(constraints), mask) => {
+                BitVec::from_fn(self.iter.data.len(), |i| constraints.selects(i, mask))
+            }
+            (None, Some(mask)) => mask.to_bitvec(),
+            (None, None) => BitVec::from_elem(self.iter.data.len(), false),
+        };
+        Some((bits, subset))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for BitVecSubsetIter<'a, T> {}
+
+impl<'a, T> FusedIterator for BitVecSubsetIter<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+
+    #[test]
+    fn masks_match_subsets() {
+        let data: Vec<usize> = (0..6).collect();
+        let generator = SubsetGenerator::new(&data, true);
+        let mut expected = 0;
+        for (mask, subset) in generator.masked_iter() {
+            assert_eq!(mask, expected);
+            let indices: Vec<usize> = (0..6).filter(|i| mask & (1 << i) != 0).collect();
+            assert_eq!(subset.into_iter().copied().collect::<Vec<_>>(), indices);
+            expected += 1;
+        }
+        assert_eq!(expected, 64);
+    }
+
+    #[test]
+    fn masks_for_64_elements() {
+        let data = vec![0; 64];
+        let generator = SubsetGenerator::new(&data, false);
+        let mut iter = generator.masked_iter();
+        assert_eq!(iter.next().map(|(mask, _)| mask), Some(1));
+        assert_eq!(iter.next().map(|(mask, _)| mask), Some(2));
+    }
+
+    #[test]
+    fn bitvecs_match_subsets() {
+        let data: Vec<usize> = (0..70).collect();
+        let generator = SubsetGenerator::new(&data, true)
+            .with_required(&[65])
+            .with_forbidden(&[0]);
+        let mut iter = generator.bitvec_iter();
+        for _ in 0..200 {
+            let (mask, subset) = iter.next().unwrap();
+            assert_eq!(mask.len(), 70);
+            let selected: Vec<&usize> = data.iter().filter(|&&i| mask[i]).collect();
+            assert_eq!(selected, subset);
+        }
+
+        let generator = SubsetGenerator::new(&data[..4], true).with_required(&[2]);
+        let masks: Vec<u64> = generator
+            .bitvec_iter()
+            .map(|(mask, _)| crate::rank::rank(&mask))
+            .collect();
+        assert_eq!(masks, vec![4, 5, 6, 7, 12, 13, 14, 15]);
+
+        let small: Vec<usize> = (0..6).collect();
+        let generator = SubsetGenerator::new(&small, true);
+        for ((bits, a), (index, b)) in generator.bitvec_iter().zip(generator.masked_iter()) {
+            assert_eq!(crate::rank::rank(&bits), index);
+            assert_eq!(a, b);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn masks_too_large() {
+        let data = vec![0; 65];
+        SubsetGenerator::new(&data, false).masked_iter();
+    }
+}
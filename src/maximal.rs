@@ -0,0 +1,174 @@
+use crate::{PruneSubsetIter, SubsetGenerator};
+use alloc::vec::Vec;
+
+/// Iterator over the maximal subsets that satisfy a monotone predicate, created
+/// by [`SubsetGenerator::maximal`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2, 3, 4];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// let maximal = sg.maximal(|s| s.len() <= 2);
+/// assert_eq!(maximal.count(), 6);
+/// ```
+pub struct MaximalSubsetIter<'a, T, P> {
+    search: PruneSubsetIter<'a, T, P>,
+    extension: Vec<&'a T>,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over the maximal feasible subsets: the subsets for
+    /// which `feasible` returns true, but for which adding any single element
+    /// makes `feasible` return false.
+    ///
+    /// Feasibility must be *downward closed* (monotone): every subset of a
+    /// feasible subset must be feasible as well, as is the case for
+    /// independent sets, packings, or budget constraints with nonnegative
+    /// costs. Under this assumption no one-element extension being feasible
+    /// means that no superset at all is feasible, and the search can skip all
+    /// supersets of an infeasible subset, like [`Prune::RejectSupersets`] does
+    /// for [`prune_iter`](Self::prune_iter). For a predicate that is not
+    /// monotone, the results are unspecified.
+    ///
+    /// The subsets are reported in the depth-first order of `prune_iter`. The
+    /// empty set is only reported, when no element is feasible on its own, if
+    /// the generator was configured to include it. Like `prune_iter`, this
+    /// respects the constraints of the generator: only free elements are
+    /// added, so a subset is maximal if no free element can be added to it.
+    ///
+    /// [`Prune::RejectSupersets`]: crate::Prune::RejectSupersets
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// // The maximal independent sets of the path 0 - 1 - 2 - 3.
+    /// let data = vec![0, 1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let independent = |s: &[&i32]| s.windows(2).all(|w| *w[1] - *w[0] > 1);
+    ///
+    /// let maximal: Vec<Vec<&i32>> = sg.maximal(independent).collect();
+    /// assert_eq!(maximal, vec![vec![&0, &2], vec![&0, &3], vec![&1, &3]]);
+    /// ```
+    pub fn maximal<P>(&self, feasible: P) -> MaximalSubsetIter<'a, T, P>
+    where
+        P: Fn(&[&T]) -> bool,
+    {
+        MaximalSubsetIter {
+            search: PruneSubsetIter::new(self.free_elements(), feasible, self.includes_base()),
+            extension: Vec::with_capacity(self.data.len()),
+        }
+    }
+}
+
+impl<'a, T, P> MaximalSubsetIter<'a, T, P>
+where
+    P: Fn(&[&T]) -> bool,
+{
+    /// Returns true if no one-element extension of the current subset of the
+    /// search is feasible.
+    fn is_maximal(&mut self) -> bool {
+        let search = &self.search;
+        let mut chosen = search.chosen.iter().peekable();
+        for i in 0..search.elements.len() {
+            if chosen.next_if_eq(&&i).is_some() {
+                continue;
+            }
+            self.extension.clear();
+            let position = search.chosen.partition_point(|&c| c < i);
+            let (before, after) = search.chosen.split_at(position);
+            let bits = before.iter().chain(Some(&i)).chain(after).copied();
+            search.elements.collect_into(bits, &mut self.extension);
+            if (search.predicate)(&self.extension) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl<'a, T, P> Iterator for MaximalSubsetIter<'a, T, P>
+where
+    P: Fn(&[&T]) -> bool,
+{
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.search.started {
+            self.search.started = true;
+            if self.search.with_emptyset {
+                let feasible = (self.search.predicate)(&self.search.current);
+                self.search.extend = feasible;
+                if feasible && self.is_maximal() {
+                    return Some(self.search.current.clone());
+                }
+            }
+        }
+
+        while self.search.advance(self.search.extend) {
+            let feasible = (self.search.predicate)(&self.search.current);
+            self.search.extend = feasible;
+            if feasible && self.is_maximal() {
+                return Some(self.search.current.clone());
+            }
+        }
+
+        // The search is exhausted, and the root has no siblings.
+        self.search.extend = false;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+
+    /// Returns true if no two elements of `s` are adjacent in the cycle
+    /// `0 - 1 - ... - 6 - 0`.
+    fn independent(s: &[&usize]) -> bool {
+        s.iter()
+            .all(|&&a| s.iter().all(|&&b| (a + 1) % 7 != b && (b + 1) % 7 != a))
+    }
+
+    #[test]
+    fn maximal_matches_brute_force() {
+        let data: Vec<usize> = (0..7).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        let mut expected: Vec<Vec<&usize>> = generator
+            .iter()
+            .filter(|s| independent(s))
+            .filter(|s| {
+                data.iter().filter(|x| !s.contains(x)).all(|x| {
+                    let mut extended = s.clone();
+                    extended.push(x);
+                    extended.sort();
+                    !independent(&extended)
+                })
+            })
+            .collect();
+        let mut maximal: Vec<Vec<&usize>> = generator.maximal(independent).collect();
+        assert_eq!(maximal.len(), 7);
+        expected.sort();
+        maximal.sort();
+        assert_eq!(maximal, expected);
+    }
+
+    #[test]
+    fn maximal_empty_set() {
+        let data = vec![5, 6];
+        let generator = SubsetGenerator::new(&data, true);
+        assert_eq!(generator.maximal(|s| s.is_empty()).count(), 1);
+        assert_eq!(generator.maximal(|_| false).count(), 0);
+        assert_eq!(
+            generator.maximal(|_| true).collect::<Vec<_>>(),
+            vec![vec![&5, &6]]
+        );
+
+        let generator = SubsetGenerator::new(&data, false);
+        assert_eq!(generator.maximal(|s| s.is_empty()).count(), 0);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::constraints::FreeElements;
+use crate::SubsetGenerator;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Finds a subset by meet-in-the-middle, in `O(2^(n/2) log 2^(n/2))` time
+    /// and `O(2^(n/2))` memory instead of the `O(2^n)` of a full enumeration.
+    ///
+    /// The dataset is split into two halves, and every subset of each half is
+    /// evaluated with `key`. A subset of the whole dataset is the union of a
+    /// left and a right part, and `combine` compares the pair of their keys to
+    /// the goal: `Equal` accepts the pair, `Less` means that the combination is
+    /// too small, and `Greater` that it is too large. The left keys are walked
+    /// upwards and the right keys downwards, so `combine` must be nondecreasing
+    /// in both arguments, as `|a, b| (a + b).cmp(&target)` is for a sum. The
+    /// returned subset keeps the order of the dataset, and is only empty if the
+    /// generator includes the empty set. The constraints of the generator are
+    /// respected: the free elements are split, in the order set by
+    /// [`with_order`](Self::with_order), and the required elements are part of
+    /// every left subset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![3, 34, 4, 12, 5, 2];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let sum = |s: &[&i32]| s.iter().copied().sum::<i32>();
+    ///
+    /// let found = sg.meet_in_middle(sum, |a, b| (a + b).cmp(&9)).unwrap();
+    /// assert_eq!(sum(&found), 9);
+    /// assert_eq!(sg.meet_in_middle(sum, |a, b| (a + b).cmp(&30)), None);
+    /// ```
+    pub fn meet_in_middle<K, F, C>(&self, key: F, combine: C) -> Option<Vec<&'a T>>
+    where
+        K: Ord,
+        F: Fn(&[&T]) -> K,
+        C: Fn(&K, &K) -> Ordering,
+    {
+        let elements = self.free_elements();
+        let bits: Vec<usize> = (0..elements.len()).collect();
+        let (left, right) = bits.split_at(bits.len() / 2);
+        let mut left = keyed_subsets(&elements, left, true, &key);
+        let mut right = keyed_subsets(&elements, right, false, &key);
+        left.sort_by(|a, b| a.0.cmp(&b.0));
+        right.sort_by(|a, b| b.0.cmp(&a.0));
+
+        let (mut i, mut j) = (0, 0);
+        while i < left.len() && j < right.len() {
+            match combine(&left[i].0, &right[j].0) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    // Every pair from the runs of equal keys is a match, so
+                    // the only one to skip is the pair of two empty parts.
+                    let run_i = run_end(&left, i);
+                    let run_j = run_end(&right, j);
+                    for l in &left[i..run_i] {
+                        for r in &right[j..run_j] {
+                            if self.includes_base() || !l.1.is_empty() || !r.1.is_empty() {
+                                let bits = l.1.iter().chain(&r.1).copied();
+                                return Some(elements.collect(bits));
+                            }
+                        }
+                    }
+                    i = run_i;
+                    j = run_j;
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Returns all the subsets of the free elements at `bits`, including the empty
+/// set, as the lists of their bits with the keys of their elements. The keys
+/// include the required elements if `with_required` is true.
+fn keyed_subsets<T, K, F>(
+    elements: &FreeElements<'_, T>,
+    bits: &[usize],
+    with_required: bool,
+    key: &F,
+) -> Vec<(K, Vec<usize>)>
+where
+    F: Fn(&[&T]) -> K,
+{
+    let mut subset = Vec::new();
+    SubsetGenerator::new(bits, true)
+        .iter()
+        .map(|chosen| {
+            let chosen: Vec<usize> = chosen.into_iter().copied().collect();
+            subset.clear();
+            elements.select_into(chosen.iter().copied(), with_required, &mut subset);
+            (key(&subset), chosen)
+        })
+        .collect()
+}
+
+/// Returns the end of the run of entries with the same key as `entries[start]`.
+fn run_end<K: Ord, V>(entries: &[(K, V)], start: usize) -> usize {
+    let key = &entries[start].0;
+    start
+        + entries[start..]
+            .iter()
+            .take_while(|(other, _)| other == key)
+            .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+
+    fn sum(subset: &[&i64]) -> i64 {
+        subset.iter().copied().sum()
+    }
+
+    #[test]
+    fn meet_in_middle_matches_search() {
+        let data: Vec<i64> = vec![7, -3, 12, 5, 9, -8, 4, 15, 1];
+        let generator = SubsetGenerator::new(&data, false);
+        for target in -15..60 {
+            let found = generator.meet_in_middle(sum, |a, b| (a + b).cmp(&target));
+            let expected = generator.find_first(|s| sum(s) == target);
+            assert_eq!(found.is_some(), expected.is_some(), "target {}", target);
+            if let Some(subset) = found {
+                assert_eq!(sum(&subset), target);
+                assert!(!subset.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn meet_in_middle_empty_set() {
+        let data: Vec<i64> = vec![1, 2, 3];
+        let target = |a: &i64, b: &i64| (a + b).cmp(&0);
+        assert_eq!(
+            SubsetGenerator::new(&data, false).meet_in_middle(sum, target),
+            None
+        );
+        assert_eq!(
+            SubsetGenerator::new(&data, true).meet_in_middle(sum, target),
+            Some(vec![])
+        );
+
+        let data: Vec<i64> = vec![2, -2, 5];
+        let found = SubsetGenerator::new(&data, false).meet_in_middle(sum, target);
+        assert_eq!(found, Some(vec![&2, &-2]));
+
+        let empty: Vec<i64> = vec![];
+        let generator = SubsetGenerator::new(&empty, false);
+        assert_eq!(generator.meet_in_middle(sum, target), None);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::SubsetGenerator;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Iterator over the multisets of a fixed size, created by
+/// [`SubsetGenerator::with_replacement`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// assert_eq!(sg.with_replacement(2).count(), 6);
+/// ```
+pub struct MultisetIter<'a, T> {
+    data: &'a [T],
+    required: Vec<usize>,
+    allowed: Vec<usize>,
+    indices: Vec<usize>,
+    first: bool,
+    exhausted: bool,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over all the multisets of exactly `k` elements of
+    /// the dataset, i.e. the combinations with repetition, of which there are
+    /// `C(n + k - 1, k)`. An element that is chosen several times appears as
+    /// repeated references. The elements of each multiset are listed in the
+    /// order of the dataset, and the multisets are reported in lexicographic
+    /// order of their indices: the last index that can still grow is
+    /// incremented, and all the indices after it are reset to its new value.
+    ///
+    /// For `k == 0` the iterator yields a single empty multiset, regardless of
+    /// whether the generator was configured to include the empty set, and for
+    /// an empty dataset and `k > 0` it yields nothing.
+    ///
+    /// The constraints of the generator are respected: every multiset holds
+    /// each required element at least once, and `k` counts these too, while
+    /// forbidden elements are never chosen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.with_replacement(2);
+    ///
+    /// assert_eq!(iter.next(), Some(vec![&1, &1]));
+    /// assert_eq!(iter.next(), Some(vec![&1, &2]));
+    /// assert_eq!(iter.next(), Some(vec![&1, &3]));
+    /// assert_eq!(iter.next(), Some(vec![&2, &2]));
+    /// assert_eq!(iter.count(), 2);
+    /// ```
+    pub fn with_replacement(&self, k: usize) -> MultisetIter<'a, T> {
+        let elements = self.free_elements();
+        let required = elements.required().to_vec();
+        let is_allowed =
+            |i: &usize| elements.bit(*i).is_some() || required.binary_search(i).is_ok();
+        let allowed: Vec<usize> = (0..self.data.len()).filter(is_allowed).collect();
+        // The multisets only choose the elements beyond the required ones.
+        let chosen = k.saturating_sub(required.len());
+        let exhausted = k < required.len() || (allowed.is_empty() && chosen > 0);
+        MultisetIter {
+            data: self.data,
+            indices: vec![0; chosen],
+            exhausted,
+            required,
+            allowed,
+            first: true,
+        }
+    }
+}
+
+impl<'a, T> MultisetIter<'a, T> {
+    /// Computes the next multiset in lexicographic order. Returns false if
+    /// every index is already at the final element, as then all multisets
+    /// have been exhausted.
+    fn next_multiset(&mut self) -> bool {
+        let last = self.allowed.len().saturating_sub(1);
+        match self.indices.iter().rposition(|&i| i < last) {
+            Some(position) => {
+                let value = self.indices[position] + 1;
+                for index in &mut self.indices[position..] {
+                    *index = value;
+                }
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<'a, T> Iterator for MultisetIter<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+        if self.first {
+            self.first = false;
+        } else if !self.next_multiset() {
+            self.exhausted = true;
+            return None;
+        }
+        let chosen = self.indices.iter().map(|&i| self.allowed[i]);
+        let mut indices: Vec<usize> = self.required.iter().copied().chain(chosen).collect();
+        indices.sort_unstable();
+        Some(indices.into_iter().map(|i| &self.data[i]).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+
+    fn binomial(n: usize, k: usize) -> usize {
+        (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+    }
+
+    #[test]
+    fn multiset_counts() {
+        let data: Vec<usize> = (0..5).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        for k in 0..6 {
+            let multisets: Vec<Vec<&usize>> = generator.with_replacement(k).collect();
+            assert_eq!(multisets.len(), binomial(data.len() + k - 1, k));
+            assert!(multisets.iter().all(|m| m.len() == k));
+            assert!(multisets.iter().all(|m| m.windows(2).all(|w| w[0] <= w[1])));
+            assert!(multisets.windows(2).all(|w| w[0] < w[1]));
+        }
+    }
+
+    #[test]
+    fn multiset_edge_cases() {
+        let empty: Vec<u32> = vec![];
+        let generator = SubsetGenerator::new(&empty, true);
+        assert_eq!(generator.with_replacement(0).count(), 1);
+        assert_eq!(generator.with_replacement(2).count(), 0);
+
+        let data = vec![7];
+        let generator = SubsetGenerator::new(&data, false);
+        let multisets: Vec<_> = generator.with_replacement(3).collect();
+        assert_eq!(multisets, vec![vec![&7, &7, &7]]);
+    }
+}
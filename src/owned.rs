@@ -0,0 +1,330 @@
+use crate::{SubsetGenerator, SubsetIter};
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::iter::{FromIterator, FusedIterator};
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+
+/// A subset generator that owns its dataset, so the caller does not have to
+/// keep a separate binding alive for as long as the generator is used. The
+/// iterators borrow from the generator itself, and behave exactly like the
+/// ones of a [`SubsetGenerator`] over the same data.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::OwnedSubsetGenerator;
+///
+/// let sg: OwnedSubsetGenerator<i32> = (1..=3).collect();
+/// assert_eq!(sg.iter().count(), 7);
+/// ```
+pub struct OwnedSubsetGenerator<T> {
+    data: Vec<T>,
+    with_emptyset: bool,
+}
+
+impl<T> OwnedSubsetGenerator<T> {
+    /// Constructs a new generator taking ownership of the dataset. If
+    /// `with_emptyset` is true, then the generator will also output the empty
+    /// vector. Otherwise, only subsets with at least one element are reported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::OwnedSubsetGenerator;
+    ///
+    /// let sg = OwnedSubsetGenerator::new(vec![1, 2, 3], true);
+    /// assert_eq!(sg.iter().count(), 8);
+    /// ```
+    pub fn new(data: Vec<T>, with_emptyset: bool) -> OwnedSubsetGenerator<T> {
+        OwnedSubsetGenerator {
+            data,
+            with_emptyset,
+        }
+    }
+
+    /// Returns a borrowing generator over the owned dataset, which gives
+    /// access to all the other ways of enumerating the subsets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::OwnedSubsetGenerator;
+    ///
+    /// let sg = OwnedSubsetGenerator::new(vec![1, 2, 3], false);
+    /// assert_eq!(sg.generator().combinations(2).count(), 3);
+    /// ```
+    pub fn generator(&self) -> SubsetGenerator<'_, T> {
+        SubsetGenerator::new(&self.data, self.with_emptyset)
+    }
+
+    /// Returns an iterator over all the subsets of the owned dataset, exactly
+    /// like [`SubsetGenerator::iter`].
+    pub fn iter(&self) -> SubsetIter<'_, T> {
+        self.generator().iter()
+    }
+
+    /// Returns the owned dataset.
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Consumes the generator and returns the dataset.
+    pub fn into_inner(self) -> Vec<T> {
+        self.data
+    }
+}
+
+/// A subset generator over the elements of a `BTreeSet` or `HashSet`, created
+/// by [`SubsetGenerator::from_btreeset`] and [`SubsetGenerator::from_hashset`].
+/// The generator owns a vector of references to the elements, collected once,
+/// so the order of the elements is fixed for its lifetime. The subsets hold
+/// references into the original set, and can outlive the generator.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeSet;
+/// use subset_generator::SubsetGenerator;
+///
+/// let set: BTreeSet<i32> = [2, 1].iter().copied().collect();
+/// let subsets: Vec<Vec<&i32>> = SubsetGenerator::from_btreeset(&set, false).iter().collect();
+/// assert_eq!(subsets, vec![vec![&1], vec![&2], vec![&1, &2]]);
+/// ```
+pub struct SetSubsetGenerator<'a, T> {
+    elements: Vec<&'a T>,
+    with_emptyset: bool,
+}
+
+/// Iterator over all the subsets of a set, created by
+/// [`SetSubsetGenerator::iter`].
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeSet;
+/// use subset_generator::SubsetGenerator;
+///
+/// let set: BTreeSet<char> = ['a', 'b', 'c'].iter().copied().collect();
+/// let sg = SubsetGenerator::from_btreeset(&set, true);
+///
+/// assert_eq!(sg.iter().len(), 8);
+/// ```
+pub struct SetSubsetIter<'s, 'a, T> {
+    iter: SubsetIter<'s, &'a T>,
+    buf: Vec<&'s &'a T>,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Constructs a generator over the elements of a `BTreeSet`, in sorted
+    /// order. The references to the elements are collected once, and the
+    /// subsets are vectors of references into the set, exactly as those of a
+    /// generator over a slice, without collecting the set first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeSet;
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let set: BTreeSet<i32> = [3, 1, 2].iter().copied().collect();
+    /// let sg = SubsetGenerator::from_btreeset(&set, false);
+    /// let mut iter = sg.iter();
+    ///
+    /// assert_eq!(iter.next(), Some(vec![&1]));
+    /// assert_eq!(iter.next(), Some(vec![&2]));
+    /// assert_eq!(iter.count(), 5);
+    /// ```
+    pub fn from_btreeset(set: &'a BTreeSet<T>, with_emptyset: bool) -> SetSubsetGenerator<'a, T> {
+        SetSubsetGenerator {
+            elements: set.iter().collect(),
+            with_emptyset,
+        }
+    }
+
+    /// Constructs a generator over the elements of a `HashSet`. The order of
+    /// the elements is the iteration order of the set, which is arbitrary,
+    /// but it is collected once and then fixed for the lifetime of the
+    /// generator. The subsets are vectors of references into the set. Only
+    /// available with the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let set: HashSet<&str> = ["a", "b", "c"].iter().copied().collect();
+    /// let sg = SubsetGenerator::from_hashset(&set, true);
+    ///
+    /// assert_eq!(sg.iter().count(), 8);
+    /// assert!(sg.iter().all(|s| s.iter().all(|&x| set.contains(x))));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_hashset<S>(
+        set: &'a HashSet<T, S>,
+        with_emptyset: bool,
+    ) -> SetSubsetGenerator<'a, T> {
+        SetSubsetGenerator {
+            elements: set.iter().collect(),
+            with_emptyset,
+        }
+    }
+}
+
+impl<'a, T> SetSubsetGenerator<'a, T> {
+    /// Returns an iterator over all the subsets of the set, in the order of
+    /// [`SubsetGenerator::iter`] over the collected elements.
+    pub fn iter(&self) -> SetSubsetIter<'_, 'a, T> {
+        SetSubsetIter {
+            iter: self.generator().iter(),
+            buf: Vec::with_capacity(self.elements.len()),
+        }
+    }
+
+    /// Returns a generator over the collected references, which gives access
+    /// to all the other ways of enumerating the subsets. Its subsets hold
+    /// references to the references, so they are tied to this generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeSet;
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let set: BTreeSet<u8> = (1..=4).collect();
+    /// let sg = SubsetGenerator::from_btreeset(&set, false);
+    /// assert_eq!(sg.generator().combinations(2).count(), 6);
+    /// ```
+    pub fn generator(&self) -> SubsetGenerator<'_, &'a T> {
+        SubsetGenerator::new(&self.elements, self.with_emptyset)
+    }
+
+    /// Returns the elements of the set, in the order of the enumeration.
+    pub fn elements(&self) -> &[&'a T] {
+        &self.elements
+    }
+}
+
+impl<'s, 'a, T> Iterator for SetSubsetIter<'s, 'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.iter.next_into(&mut self.buf) {
+            return None;
+        }
+        Some(self.buf.iter().map(|&&x| x).collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'s, 'a, T> DoubleEndedIterator for SetSubsetIter<'s, 'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let subset = self.iter.next_back()?;
+        Some(subset.into_iter().copied().collect())
+    }
+}
+
+impl<'s, 'a, T> ExactSizeIterator for SetSubsetIter<'s, 'a, T> {}
+
+impl<'s, 'a, T> FusedIterator for SetSubsetIter<'s, 'a, T> {}
+
+impl<'s, 'a, T> IntoIterator for &'s SetSubsetGenerator<'a, T> {
+    type Item = Vec<&'a T>;
+    type IntoIter = SetSubsetIter<'s, 'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Collects a dataset into a generator that does not include the empty set.
+impl<T> FromIterator<T> for OwnedSubsetGenerator<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        OwnedSubsetGenerator::new(iter.into_iter().collect(), false)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a OwnedSubsetGenerator<T> {
+    type Item = Vec<&'a T>;
+    type IntoIter = SubsetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{OwnedSubsetGenerator, SubsetGenerator};
+
+    fn build(n: u32) -> OwnedSubsetGenerator<u32> {
+        (0..n).map(|i| i * i).collect()
+    }
+
+    #[test]
+    fn owned_matches_borrowed() {
+        let owned = build(6);
+        let data = owned.data().to_vec();
+        let borrowed = SubsetGenerator::new(&data, false);
+        assert!(owned.iter().eq(borrowed.iter()));
+
+        let owned = OwnedSubsetGenerator::new(data.clone(), true);
+        let borrowed = SubsetGenerator::new(&data, true);
+        let mut iters = 0;
+        for (a, b) in (&owned).into_iter().zip(borrowed.iter()) {
+            assert_eq!(a, b);
+            iters += 1;
+        }
+        assert_eq!(iters, 64);
+        assert_eq!(owned.into_inner(), data);
+    }
+
+    #[test]
+    fn from_btreeset_sorted() {
+        use std::collections::BTreeSet;
+
+        let data: Vec<u32> = vec![9, 4, 7, 1, 5];
+        let tree: BTreeSet<u32> = data.iter().copied().collect();
+        let sg = SubsetGenerator::from_btreeset(&tree, true);
+        let sorted: Vec<u32> = tree.iter().copied().collect();
+        let expected = SubsetGenerator::new(&sorted, true);
+        assert!(sg
+            .iter()
+            .map(|s| s.into_iter().copied().collect::<Vec<_>>())
+            .eq(expected.iter_cloned()));
+
+        // The subsets borrow from the set, not from the generator.
+        let subsets: Vec<Vec<&u32>> = {
+            let sg = SubsetGenerator::from_btreeset(&tree, false);
+            sg.iter().rev().collect()
+        };
+        assert_eq!(subsets[0], tree.iter().collect::<Vec<_>>());
+        assert_eq!(subsets.len(), 31);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn from_hashset_stable() {
+        use std::collections::HashSet;
+
+        let data: Vec<u32> = vec![9, 4, 7, 1, 5];
+        let hash: HashSet<u32> = data.iter().copied().collect();
+        let sg = SubsetGenerator::from_hashset(&hash, false);
+        let first: Vec<Vec<u32>> = sg
+            .iter()
+            .map(|s| s.into_iter().copied().collect())
+            .collect();
+        assert_eq!(first.len(), 31);
+        let full = first.last().unwrap();
+        assert_eq!(full.iter().copied().collect::<HashSet<_>>(), hash);
+        let again: Vec<Vec<u32>> = sg
+            .iter()
+            .map(|s| s.into_iter().copied().collect())
+            .collect();
+        assert_eq!(first, again);
+    }
+}
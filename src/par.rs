@@ -0,0 +1,395 @@
+use crate::predicate::{any_matching_in, count_matching_in, for_each_in};
+use crate::{SubsetGenerator, SubsetIter};
+use core::convert::TryFrom;
+use core::sync::atomic::{AtomicBool, Ordering};
+use rayon::iter::plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer};
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+
+/// Parallel iterator over all the subsets, created by
+/// [`SubsetGenerator::par_iter`]. Only available with the `rayon` feature.
+///
+/// # Examples
+///
+/// ```
+/// use rayon::prelude::*;
+/// use subset_generator::SubsetGenerator;
+///
+/// let data = vec![1, 2, 3, 4];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// assert_eq!(sg.par_iter().filter(|s| s.len() == 2).count(), 6);
+/// ```
+pub struct ParSubsetIter<'a, T> {
+    generator: SubsetGenerator<'a, T>,
+    len: usize,
+}
+
+/// Producer over the subsets with an index in `[start, end)`, which is split
+/// recursively by rayon. Each part reconstructs its first subset from its
+/// index, and then enumerates its range sequentially.
+struct SubsetProducer<'a, T> {
+    generator: SubsetGenerator<'a, T>,
+    start: u128,
+    end: u128,
+}
+
+impl<'a, T: Sync> SubsetGenerator<'a, T> {
+    /// Returns a parallel iterator over all the subsets. The subsets are
+    /// distributed over the rayon thread pool by recursively splitting the
+    /// index range (see [`rank`](crate::rank)), so every subset is still
+    /// reported exactly once, and the empty set only if the generator was
+    /// configured to include it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of subsets does not fit in a `usize`, which is the
+    /// length of an indexed parallel iterator. This is the case for datasets
+    /// of more than 64 elements, of 64 elements with the empty set, and of 32
+    /// or more elements on 32-bit targets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![3, 34, 4, 12, 5, 2];
+    /// let sg = SubsetGenerator::new(&data, false);
+    ///
+    /// let found = sg.par_iter().any(|s| s.into_iter().sum::<i32>() == 9);
+    /// assert!(found);
+    /// ```
+    pub fn par_iter(&self) -> ParSubsetIter<'a, T> {
+        let len = (self.free_len() <= 64)
+            .then(|| (1u128 << self.free_len()) - !self.includes_base() as u128)
+            .and_then(|total| usize::try_from(total).ok())
+            .expect("par_iter supports at most usize::MAX subsets");
+        ParSubsetIter {
+            generator: self.clone(),
+            len,
+        }
+    }
+
+    /// Returns the number of subsets that satisfy `predicate`, like
+    /// [`count_matching`](SubsetGenerator::count_matching), but evaluated on the
+    /// rayon thread pool. The index range is split into several chunks per
+    /// thread with [`split`](SubsetGenerator::split), and each chunk is counted
+    /// sequentially with its own reused buffer. For expensive predicates this
+    /// scales across all cores.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dataset has more than 64 elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3, 4];
+    /// let sg = SubsetGenerator::new(&data, true);
+    ///
+    /// assert_eq!(sg.par_count_matching(|s| s.len() == 2), 6);
+    /// ```
+    pub fn par_count_matching<P>(&self, predicate: P) -> u64
+    where
+        P: Fn(&[&T]) -> bool + Sync,
+    {
+        self.split(rayon::current_num_threads() * 4)
+            .into_par_iter()
+            .map(|chunk| count_matching_in(chunk, &predicate))
+            .sum()
+    }
+
+    /// Returns true if at least one subset satisfies `predicate`, like
+    /// [`any_match`](SubsetGenerator::any_match), but evaluated on the rayon
+    /// thread pool. The chunks of [`split`](SubsetGenerator::split) are
+    /// searched concurrently, each with its own reused buffer, and all of them
+    /// stop as soon as one finds a match.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dataset has more than 64 elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data: Vec<u32> = (1..=16).collect();
+    /// let sg = SubsetGenerator::new(&data, false);
+    ///
+    /// assert!(sg.par_any_match(|s| s.iter().copied().sum::<u32>() == 100));
+    /// assert!(!sg.par_any_match(|s| s.len() > 16));
+    /// ```
+    pub fn par_any_match<P>(&self, predicate: P) -> bool
+    where
+        P: Fn(&[&T]) -> bool + Sync,
+    {
+        let found = AtomicBool::new(false);
+        self.split(rayon::current_num_threads() * 4)
+            .into_par_iter()
+            .for_each(|chunk| {
+                if any_matching_in(chunk, &predicate, || found.load(Ordering::Relaxed)) {
+                    found.store(true, Ordering::Relaxed);
+                }
+            });
+        found.into_inner()
+    }
+
+    /// Returns true if every subset satisfies `predicate`, like
+    /// [`all_match`](SubsetGenerator::all_match), but evaluated on the rayon
+    /// thread pool as with [`par_any_match`](SubsetGenerator::par_any_match).
+    /// All the chunks stop as soon as one finds a subset that fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dataset has more than 64 elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data: Vec<u32> = (1..=16).collect();
+    /// let sg = SubsetGenerator::new(&data, false);
+    ///
+    /// assert!(sg.par_all_match(|s| s.iter().copied().sum::<u32>() <= 136));
+    /// assert!(!sg.par_all_match(|s| s.len() < 16));
+    /// ```
+    pub fn par_all_match<P>(&self, predicate: P) -> bool
+    where
+        P: Fn(&[&T]) -> bool + Sync,
+    {
+        !self.par_any_match(|s| !predicate(s))
+    }
+
+    /// Calls `f` on every subset, like [`for_each`](SubsetGenerator::for_each),
+    /// but on the rayon thread pool. The chunks of
+    /// [`split`](SubsetGenerator::split) are processed concurrently, each with
+    /// its own reused buffer, so the subsets are visited in no particular
+    /// order. As the closure is shared between the threads, it must be `Sync`
+    /// and can only mutate state through synchronization, such as atomics, a
+    /// `Mutex`, or a channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dataset has more than 64 elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3, 4];
+    /// let sg = SubsetGenerator::new(&data, false);
+    ///
+    /// let total = AtomicUsize::new(0);
+    /// sg.par_for_each(|s| {
+    ///     total.fetch_add(s.len(), Ordering::Relaxed);
+    /// });
+    /// assert_eq!(total.into_inner(), 4 * 8);
+    /// ```
+    pub fn par_for_each<F>(&self, f: F)
+    where
+        F: Fn(&[&'a T]) + Sync,
+    {
+        self.split(rayon::current_num_threads() * 4)
+            .into_par_iter()
+            .for_each(|chunk| for_each_in(chunk, &f));
+    }
+}
+
+impl<'a, T: Sync> ParallelIterator for ParSubsetIter<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(IndexedParallelIterator::len(self))
+    }
+}
+
+impl<'a, T: Sync> IndexedParallelIterator for ParSubsetIter<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: Consumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        // Without the empty set, the enumeration starts at index 1.
+        let start = !self.generator.includes_base() as u128;
+        let end = 1u128 << self.generator.free_len();
+        callback.callback(SubsetProducer {
+            generator: self.generator,
+            start,
+            end,
+        })
+    }
+}
+
+impl<'a, T: Sync> Producer for SubsetProducer<'a, T> {
+    type Item = Vec<&'a T>;
+    type IntoIter = SubsetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.generator.range(self.start, self.end)
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let mid = self.start + index as u128;
+        let left = SubsetProducer {
+            generator: self.generator.clone(),
+            start: self.start,
+            end: mid,
+        };
+        let right = SubsetProducer {
+            generator: self.generator,
+            start: mid,
+            end: self.end,
+        };
+        (left, right)
+    }
+}
+
+impl<'a, T: Sync> IntoParallelIterator for SubsetGenerator<'a, T> {
+    type Item = Vec<&'a T>;
+    type Iter = ParSubsetIter<'a, T>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.par_iter()
+    }
+}
+
+impl<'a, T: Sync> IntoParallelIterator for &SubsetGenerator<'a, T> {
+    type Item = Vec<&'a T>;
+    type Iter = ParSubsetIter<'a, T>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.par_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+    use rayon::prelude::*;
+
+    #[test]
+    fn par_iter_matches_iter() {
+        let data: Vec<usize> = (0..10).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let expected: Vec<_> = generator.iter().collect();
+            let collected: Vec<_> = generator.par_iter().collect();
+            assert_eq!(collected, expected);
+            assert_eq!(generator.par_iter().len(), expected.len());
+        }
+    }
+
+    #[test]
+    fn par_iter_filter_count() {
+        let data: Vec<u32> = (1..=16).collect();
+        let generator = SubsetGenerator::new(&data, true);
+        let count = (&generator)
+            .into_par_iter()
+            .filter(|s| s.iter().copied().sum::<u32>() % 2 == 0)
+            .count();
+        let expected = generator
+            .iter()
+            .filter(|s| s.iter().copied().sum::<u32>() % 2 == 0)
+            .count();
+        assert_eq!(count, expected);
+    }
+
+    #[test]
+    fn par_count_matching_matches_serial() {
+        let data: Vec<u32> = (1..=16).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset).with_required(&[3]);
+            let even = |s: &[&u32]| s.iter().copied().sum::<u32>() % 2 == 0;
+            assert_eq!(
+                generator.par_count_matching(even),
+                generator.count_matching(even)
+            );
+        }
+        let empty: Vec<u32> = vec![];
+        let generator = SubsetGenerator::new(&empty, true);
+        assert_eq!(generator.par_count_matching(|s| s.is_empty()), 1);
+    }
+
+    #[test]
+    fn par_any_all_match_matches_serial() {
+        let data: Vec<u32> = (1..=14).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset).with_forbidden(&[4]);
+            let sum = |s: &[&u32]| s.iter().copied().sum::<u32>();
+            for &bound in &[0, 1, 50, 100, 101, 200] {
+                let below = |s: &[&u32]| sum(s) < bound;
+                assert_eq!(generator.par_any_match(below), generator.any_match(below));
+                assert_eq!(generator.par_all_match(below), generator.all_match(below));
+            }
+        }
+        let empty: Vec<u32> = vec![];
+        let generator = SubsetGenerator::new(&empty, true);
+        assert!(generator.par_any_match(|s| s.is_empty()));
+        assert!(!generator.par_all_match(|s| !s.is_empty()));
+    }
+
+    #[test]
+    fn par_for_each_matches_serial() {
+        use std::sync::Mutex;
+
+        let data: Vec<u32> = (0..12).collect();
+        let generator = SubsetGenerator::new(&data, true).with_required(&[7]);
+        let visited = Mutex::new(Vec::new());
+        generator.par_for_each(|s| visited.lock().unwrap().push(s.to_vec()));
+        let mut visited = visited.into_inner().unwrap();
+        visited.sort();
+
+        let mut expected: Vec<_> = generator.iter().collect();
+        expected.sort();
+        assert_eq!(visited, expected);
+    }
+
+    #[test]
+    fn par_iter_empty_dataset() {
+        let data: Vec<u32> = vec![];
+        assert_eq!(SubsetGenerator::new(&data, true).par_iter().count(), 1);
+        assert_eq!(SubsetGenerator::new(&data, false).par_iter().count(), 0);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn par_iter_sixty_four_elements() {
+        let data: Vec<usize> = (0..64).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        assert_eq!(generator.par_iter().len(), usize::MAX);
+        let first: Vec<_> = generator.par_iter().take(3).collect();
+        assert_eq!(first, vec![vec![&0], vec![&1], vec![&0, &1]]);
+        let last: Vec<_> = generator.par_iter().rev().take(1).collect();
+        assert_eq!(last, vec![data.iter().collect::<Vec<_>>()]);
+    }
+
+    #[test]
+    #[should_panic(expected = "par_iter supports at most usize::MAX subsets")]
+    fn par_iter_too_many_subsets() {
+        let data: Vec<usize> = (0..64).collect();
+        SubsetGenerator::new(&data, true).par_iter();
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{SubsetGenerator, SubsetIter};
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// Iterator over all the subsets in increasing order of their number of
+/// elements, created by [`SubsetGenerator::by_popcount`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, true);
+///
+/// let sizes: Vec<usize> = sg.by_popcount().map(|s| s.len()).collect();
+/// assert_eq!(sizes, vec![0, 1, 1, 1, 2, 2, 2, 3]);
+/// ```
+pub struct PopcountOrderedIter<'a, T> {
+    iter: SubsetIter<'a, T>,
+    popcount: usize,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over all the subsets of `iter`, ordered by the
+    /// number of set bits of their masks (their Hamming weight), and subsets
+    /// of the same size by increasing index. Only the current popcount is
+    /// tracked: each layer is walked by jumping between the masks with that
+    /// many set bits, as [`SubsetIter::next_with_popcount`] does, so no layer
+    /// is materialized. Unlike [`by_cardinality`](Self::by_cardinality), the
+    /// subsets are reported one by one, so this is a drop-in replacement for
+    /// `iter` where small subsets should come first.
+    ///
+    /// The constraints of the generator are respected, in which case the
+    /// popcount counts the free elements and the required elements are added
+    /// to every subset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.by_popcount();
+    ///
+    /// assert_eq!(iter.next(), Some(vec![&1]));
+    /// assert_eq!(iter.next(), Some(vec![&2]));
+    /// assert_eq!(iter.next(), Some(vec![&3]));
+    /// assert_eq!(iter.next(), Some(vec![&1, &2]));
+    /// ```
+    pub fn by_popcount(&self) -> PopcountOrderedIter<'a, T> {
+        PopcountOrderedIter {
+            iter: self.iter(),
+            popcount: !self.includes_base() as usize,
+        }
+    }
+}
+
+impl<'a, T> Iterator for PopcountOrderedIter<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.popcount <= self.iter.set.len() {
+            if let Some(subset) = self.iter.next_with_popcount(self.popcount) {
+                return Some(subset);
+            }
+            // The layer is exhausted, so start over with the next one.
+            self.popcount += 1;
+            self.iter.reset();
+        }
+        None
+    }
+}
+
+impl<'a, T> FusedIterator for PopcountOrderedIter<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+
+    #[test]
+    fn popcount_nondecreasing() {
+        let data: Vec<usize> = (0..8).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let constrained = generator.clone().with_required(&[6]).with_forbidden(&[1]);
+            for sg in &[generator, constrained] {
+                let subsets: Vec<_> = sg.by_popcount().collect();
+                assert!(subsets.windows(2).all(|w| w[0].len() <= w[1].len()));
+
+                // Ties are broken by the order of `iter`.
+                let mut expected: Vec<_> = sg.iter().collect();
+                expected.sort_by_key(|s| s.len());
+                assert_eq!(subsets, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn popcount_small_datasets() {
+        let empty: Vec<usize> = vec![];
+        assert_eq!(SubsetGenerator::new(&empty, false).by_popcount().count(), 0);
+        assert_eq!(SubsetGenerator::new(&empty, true).by_popcount().count(), 1);
+
+        let data = vec![1, 2];
+        let required = SubsetGenerator::new(&data, false).with_required(&[0, 1]);
+        let mut iter = required.by_popcount();
+        assert_eq!(iter.next(), Some(vec![&1, &2]));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+}
@@ -0,0 +1,563 @@
+use crate::{SubsetGenerator, SubsetIter};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::hash::Hash;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns the first subset, in the order of `iter`, that satisfies
+    /// `predicate`, or `None` if no subset does. The enumeration stops at the
+    /// first match. The subsets are generated into a single reused buffer, so
+    /// only the subset that matches is allocated, and the predicate receives a
+    /// plain slice, which avoids the lifetime juggling of `iter().find(..)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![3, 34, 4, 12, 5, 2];
+    /// let sg = SubsetGenerator::new(&data, false);
+    ///
+    /// let sum = |s: &[&i32]| s.iter().copied().sum::<i32>();
+    /// assert_eq!(sg.find_first(|s| sum(s) == 9), Some(vec![&4, &5]));
+    /// assert_eq!(sg.find_first(|s| sum(s) == 100), None);
+    /// ```
+    pub fn find_first<P>(&self, predicate: P) -> Option<Vec<&'a T>>
+    where
+        P: Fn(&[&T]) -> bool,
+    {
+        let mut iter = self.iter();
+        let mut buf = Vec::with_capacity(self.data.len());
+        while iter.next_into(&mut buf) {
+            if predicate(&buf) {
+                return Some(buf);
+            }
+        }
+        None
+    }
+
+    /// Returns the index of the first subset, in the order of `iter`, that
+    /// satisfies `predicate`, or `None` if no subset does. The index is the one
+    /// used by [`SubsetIter::seek`], so a subset that was found can be recorded
+    /// and revisited later. The enumeration stops at the first match, and the
+    /// subsets are generated into a single reused buffer, as with
+    /// [`find_first`](Self::find_first).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index of a subset does not fit in a `u64`, which can only
+    /// happen for datasets of more than 64 elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![3, 34, 4, 12, 5, 2];
+    /// let sg = SubsetGenerator::new(&data, false);
+    ///
+    /// let index = sg.position_of(|s| s.iter().copied().sum::<i32>() == 9);
+    /// assert_eq!(index, Some(0b10100));
+    /// assert_eq!(sg.iter().seek(0b10100), Some(vec![&4, &5]));
+    /// ```
+    pub fn position_of<P>(&self, predicate: P) -> Option<u64>
+    where
+        P: Fn(&[&T]) -> bool,
+    {
+        let mut iter = self.iter();
+        let mut buf = Vec::with_capacity(self.data.len());
+        loop {
+            // The pending base subset is reported before the cursor moves.
+            let base = iter.with_emptyset;
+            if !iter.next_into(&mut buf) {
+                return None;
+            }
+            if predicate(&buf) {
+                return Some(if base { 0 } else { iter.current_index() });
+            }
+        }
+    }
+
+    /// Returns the number of subsets that satisfy `predicate`. The subsets are
+    /// generated into a single reused buffer with
+    /// [`next_into`](SubsetIter::next_into), so counting performs no
+    /// allocations beyond that buffer. With the `rayon` feature,
+    /// `par_count_matching` spreads the work over all cores.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3, 4];
+    /// let sg = SubsetGenerator::new(&data, true);
+    ///
+    /// assert_eq!(sg.count_matching(|s| s.len() == 2), 6);
+    /// assert_eq!(sg.count_matching(|s| s.iter().copied().sum::<i32>() > 5), 7);
+    /// ```
+    pub fn count_matching<P>(&self, predicate: P) -> u64
+    where
+        P: Fn(&[&T]) -> bool,
+    {
+        count_matching_in(self.iter(), &predicate)
+    }
+
+    /// Returns true if at least one subset satisfies `predicate`. The
+    /// enumeration stops at the first match, and the subsets are generated
+    /// into a single reused buffer, so unlike `iter().any(..)` no subset is
+    /// allocated at all. With the `rayon` feature, `par_any_match` spreads
+    /// the work over all cores.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![3, 34, 4, 12, 5, 2];
+    /// let sg = SubsetGenerator::new(&data, false);
+    ///
+    /// let sum = |s: &[&i32]| s.iter().copied().sum::<i32>();
+    /// assert!(sg.any_match(|s| sum(s) == 9));
+    /// assert!(!sg.any_match(|s| sum(s) == 30));
+    /// ```
+    pub fn any_match<P>(&self, predicate: P) -> bool
+    where
+        P: Fn(&[&T]) -> bool,
+    {
+        any_matching_in(self.iter(), &predicate, || false)
+    }
+
+    /// Returns true if every subset satisfies `predicate`, which holds
+    /// vacuously if there are no subsets. The enumeration stops at the first
+    /// subset that fails, and the subsets are generated into a single reused
+    /// buffer, as with [`any_match`](Self::any_match). With the `rayon`
+    /// feature, `par_all_match` spreads the work over all cores.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    ///
+    /// assert!(sg.all_match(|s| !s.is_empty()));
+    /// assert!(!sg.all_match(|s| s.len() < 3));
+    /// ```
+    pub fn all_match<P>(&self, predicate: P) -> bool
+    where
+        P: Fn(&[&T]) -> bool,
+    {
+        !self.any_match(|s| !predicate(s))
+    }
+
+    /// Calls `f` on every subset, in the order of `iter`. The subsets are
+    /// generated into a single reused buffer, so the sweep performs no
+    /// allocations beyond that buffer. This is the imperative counterpart of
+    /// [`count_matching`](SubsetGenerator::count_matching), for side effects
+    /// such as gathering statistics. The closure may mutate its environment
+    /// and needs neither `Sync` nor `Send`; with the `rayon` feature,
+    /// `par_for_each` runs a shared closure on all cores.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, true);
+    ///
+    /// let mut sizes = vec![0; 4];
+    /// sg.for_each(|s| sizes[s.len()] += 1);
+    /// assert_eq!(sizes, vec![1, 3, 3, 1]);
+    /// ```
+    pub fn for_each<F>(&self, f: F)
+    where
+        F: FnMut(&[&'a T]),
+    {
+        for_each_in(self.iter(), f)
+    }
+
+    /// Folds `step` over every subset, in the order of `iter`, and calls
+    /// `report` with the current accumulator and the number of subsets seen so
+    /// far after every `report_interval` subsets. This gives anytime behavior
+    /// to long searches: `report` can log the progress or record the best
+    /// solution found so far. The interval is set with
+    /// [`report_interval`](crate::SubsetGeneratorBuilder::report_interval) on
+    /// the builder. As with `for_each`, the subsets are generated into a single
+    /// reused buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![4, 1, 3, 2];
+    /// let sg = SubsetGenerator::builder(&data).report_interval(4).build().unwrap();
+    ///
+    /// let mut reports = Vec::new();
+    /// let best = sg.search(
+    ///     0,
+    ///     |best, s| best.max(s.iter().copied().sum::<i32>() * (s.len() <= 2) as i32),
+    ///     |best, seen| reports.push((*best, seen)),
+    /// );
+    /// assert_eq!(best, 7);
+    /// assert_eq!(reports, vec![(5, 4), (7, 8), (7, 12)]);
+    /// ```
+    pub fn search<S, F, R>(&self, init: S, mut step: F, mut report: R) -> S
+    where
+        F: FnMut(S, &[&'a T]) -> S,
+        R: FnMut(&S, u64),
+    {
+        let mut iter = self.iter();
+        let mut buf = Vec::with_capacity(self.data.len());
+        let mut state = init;
+        let mut seen = 0u64;
+        while iter.next_into(&mut buf) {
+            state = step(state, &buf);
+            seen += 1;
+            if seen % self.report_interval == 0 {
+                report(&state, seen);
+            }
+        }
+        state
+    }
+
+    /// Returns how many subsets map to each value of `key`, such as the number
+    /// of subsets that reach each sum. The subsets are those of `iter`, so the
+    /// empty set is only counted if the generator includes it, and they are
+    /// generated into a single reused buffer. Only available with the `std`
+    /// feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, true);
+    /// let sums = sg.histogram(|s| s.iter().copied().sum::<i32>());
+    ///
+    /// assert_eq!(sums[&3], 2);
+    /// assert_eq!(sums[&0], 1);
+    /// assert_eq!(sums.len(), 7);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn histogram<K, F>(&self, key: F) -> HashMap<K, u64>
+    where
+        K: Eq + Hash,
+        F: Fn(&[&T]) -> K,
+    {
+        let mut counts = HashMap::new();
+        self.for_each(|subset| *counts.entry(key(subset)).or_insert(0) += 1);
+        counts
+    }
+
+    /// Returns the subset with the largest `key`, together with that key, or
+    /// `None` if there are no subsets. Of several subsets with the largest
+    /// key, the first in the order of `iter` is returned. Every subset is
+    /// evaluated, using a single reused buffer, so only the best subset found
+    /// so far is allocated. Use `Reverse` keys to minimize instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let weights = vec![4, 7, 2, 5];
+    /// let sg = SubsetGenerator::new(&weights, false);
+    ///
+    /// // The heaviest subset that fits in a knapsack of capacity 10.
+    /// let (weight, subset) = sg
+    ///     .best_by_key(|s| {
+    ///         let total = s.iter().copied().sum::<i32>();
+    ///         if total <= 10 { total } else { -1 }
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(weight, 9);
+    /// assert_eq!(subset, vec![&7, &2]);
+    /// ```
+    pub fn best_by_key<K, F>(&self, key: F) -> Option<(K, Vec<&'a T>)>
+    where
+        K: Ord,
+        F: Fn(&[&T]) -> K,
+    {
+        best_by_key_in(self.iter(), &key)
+    }
+}
+
+/// Counts the remaining subsets of `iter` that satisfy `predicate`, reusing a
+/// single buffer for all of them.
+pub(crate) fn count_matching_in<'a, T, P>(mut iter: SubsetIter<'a, T>, predicate: &P) -> u64
+where
+    P: Fn(&[&T]) -> bool,
+{
+    let mut buf = Vec::with_capacity(iter.data.len());
+    let mut count = 0;
+    while iter.next_into(&mut buf) {
+        count += predicate(&buf) as u64;
+    }
+    count
+}
+
+/// Returns true if a remaining subset of `iter` satisfies `predicate`, reusing
+/// a single buffer for all of them. The search also gives up, returning false,
+/// as soon as `stop` returns true, which lets concurrent searches cancel each
+/// other.
+pub(crate) fn any_matching_in<'a, T, P, S>(
+    mut iter: SubsetIter<'a, T>,
+    predicate: &P,
+    stop: S,
+) -> bool
+where
+    P: Fn(&[&T]) -> bool,
+    S: Fn() -> bool,
+{
+    let mut buf = Vec::with_capacity(iter.data.len());
+    while !stop() && iter.next_into(&mut buf) {
+        if predicate(&buf) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Calls `f` on the remaining subsets of `iter`, reusing a single buffer for
+/// all of them.
+pub(crate) fn for_each_in<'a, T, F>(mut iter: SubsetIter<'a, T>, mut f: F)
+where
+    F: FnMut(&[&'a T]),
+{
+    let mut buf = Vec::with_capacity(iter.data.len());
+    while iter.next_into(&mut buf) {
+        f(&buf);
+    }
+}
+
+/// Returns the remaining subset of `iter` with the largest `key`, preferring
+/// the first on ties. This is the full enumeration; a search that skips parts
+/// of the subsets by an upper bound on the key can share the same bookkeeping.
+pub(crate) fn best_by_key_in<'a, T, K, F>(
+    mut iter: SubsetIter<'a, T>,
+    key: &F,
+) -> Option<(K, Vec<&'a T>)>
+where
+    K: Ord,
+    F: Fn(&[&T]) -> K,
+{
+    let mut buf = Vec::with_capacity(iter.data.len());
+    let mut best: Option<(K, Vec<&'a T>)> = None;
+    while iter.next_into(&mut buf) {
+        let value = key(&buf);
+        if best.as_ref().map_or(true, |(top, _)| value > *top) {
+            match &mut best {
+                Some((top, subset)) => {
+                    *top = value;
+                    subset.clear();
+                    subset.extend_from_slice(&buf);
+                }
+                None => best = Some((value, buf.clone())),
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+    use std::cell::Cell;
+
+    #[test]
+    fn find_first_matches_find() {
+        let data: Vec<u32> = (1..=10).collect();
+        let generator = SubsetGenerator::new(&data, true);
+        for target in 0..60 {
+            let sum = |s: &[&u32]| s.iter().copied().sum::<u32>();
+            let expected = generator.iter().find(|s| sum(s) == target);
+            assert_eq!(generator.find_first(|s| sum(s) == target), expected);
+        }
+    }
+
+    #[test]
+    fn count_matching_matches_filter() {
+        let data: Vec<u32> = (1..=10).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset).with_forbidden(&[2]);
+            let even = |s: &[&u32]| s.iter().copied().sum::<u32>() % 2 == 0;
+            let expected = generator.iter().filter(|s| even(s)).count() as u64;
+            assert_eq!(generator.count_matching(even), expected);
+        }
+    }
+
+    #[test]
+    fn find_first_stops_early() {
+        let data: Vec<u32> = (0..20).collect();
+        let generator = SubsetGenerator::new(&data, false).with_required(&[5]);
+        let calls = Cell::new(0);
+        let found = generator.find_first(|s| {
+            calls.set(calls.get() + 1);
+            s.len() == 3
+        });
+        assert_eq!(found, Some(vec![&0, &1, &5]));
+        assert_eq!(calls.get(), 4);
+    }
+
+    #[test]
+    fn any_all_match_adapters() {
+        let data: Vec<u32> = (1..=8).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset).with_required(&[1]);
+            let sum = |s: &[&u32]| s.iter().copied().sum::<u32>();
+            for bound in 0..40 {
+                let below = |s: &[&u32]| sum(s) < bound;
+                assert_eq!(
+                    generator.any_match(below),
+                    generator.iter().any(|s| below(&s))
+                );
+                assert_eq!(
+                    generator.all_match(below),
+                    generator.iter().all(|s| below(&s))
+                );
+            }
+        }
+
+        let empty: Vec<u32> = vec![];
+        let generator = SubsetGenerator::new(&empty, false);
+        assert!(!generator.any_match(|_| true));
+        assert!(generator.all_match(|_| false));
+    }
+
+    #[test]
+    fn any_match_stops_early() {
+        let data: Vec<u32> = (0..20).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        let calls = Cell::new(0);
+        assert!(generator.any_match(|s| {
+            calls.set(calls.get() + 1);
+            s.len() == 2
+        }));
+        assert_eq!(calls.get(), 3);
+        calls.set(0);
+        assert!(!generator.all_match(|s| {
+            calls.set(calls.get() + 1);
+            s.len() < 2
+        }));
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn for_each_visits_every_subset() {
+        let data: Vec<u32> = (1..=8).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset).with_forbidden(&[0]);
+            let mut visited = Vec::new();
+            generator.for_each(|s| visited.push(s.to_vec()));
+            assert_eq!(visited, generator.iter().collect::<Vec<_>>());
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn histogram_of_sizes() {
+        let data: Vec<u8> = (0..10).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let sizes = generator.histogram(|s| s.len());
+            let mut binomial = 1;
+            for k in 0..=10 {
+                let expected = if k == 0 && !with_emptyset {
+                    None
+                } else {
+                    Some(&binomial)
+                };
+                assert_eq!(sizes.get(&k), expected);
+                binomial = binomial * (10 - k as u64) / (k as u64 + 1);
+            }
+            assert_eq!(sizes.values().sum::<u64>(), generator.iter().len() as u64);
+        }
+    }
+
+    #[test]
+    fn best_by_key_matches_max() {
+        let data: Vec<i32> = vec![6, -2, 9, 4, -7, 3, 1];
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset).with_forbidden(&[2]);
+            let score = |s: &[&i32]| {
+                let sum = s.iter().copied().sum::<i32>();
+                (sum % 5, core::cmp::Reverse(s.len()))
+            };
+            let expected = generator.iter().map(|s| (score(&s), s)).fold(
+                None,
+                |best: Option<(_, Vec<&i32>)>, (k, s)| match best {
+                    Some((top, _)) if top >= k => best,
+                    _ => Some((k, s)),
+                },
+            );
+            assert_eq!(generator.best_by_key(score), expected);
+        }
+
+        let empty: Vec<i32> = vec![];
+        assert_eq!(
+            SubsetGenerator::new(&empty, false).best_by_key(|s| s.len()),
+            None
+        );
+        assert_eq!(
+            SubsetGenerator::new(&empty, true).best_by_key(|s| s.len()),
+            Some((0, vec![]))
+        );
+    }
+
+    #[test]
+    fn search_reports_periodically() {
+        let data: Vec<u32> = (0..7).collect();
+        let generator = SubsetGenerator::builder(&data)
+            .emptyset(true)
+            .require(&[2])
+            .report_interval(10)
+            .build()
+            .unwrap();
+        let mut seen = Vec::new();
+        let mut reports = Vec::new();
+        let count = generator.search(
+            0u64,
+            |count, s| {
+                seen.push(s.to_vec());
+                count + 1
+            },
+            |&count, at| reports.push((count, at)),
+        );
+        assert_eq!(seen, generator.iter().collect::<Vec<_>>());
+        assert_eq!(count, 64);
+        let expected: Vec<_> = (1..=6).map(|i| (i * 10, i * 10)).collect();
+        assert_eq!(reports, expected);
+
+        let default = SubsetGenerator::new(&data, false);
+        let mut reported = false;
+        default.search((), |_, _| (), |_, _| reported = true);
+        assert!(!reported);
+    }
+
+    #[test]
+    fn position_of_seeks_back() {
+        let data: Vec<u32> = vec![9, 4, 7, 1, 6, 3];
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let constrained = generator.clone().with_required(&[2]).with_forbidden(&[0]);
+            for sg in &[generator, constrained] {
+                for target in 0..40 {
+                    let matches = |s: &[&u32]| s.iter().copied().sum::<u32>() == target;
+                    let index = sg.position_of(matches);
+                    assert_eq!(index.is_some(), sg.find_first(matches).is_some());
+                    if let Some(index) = index {
+                        let subset = sg.iter().seek(index).unwrap();
+                        assert!(matches(&subset));
+                        assert_eq!(Some(subset), sg.find_first(matches));
+                    }
+                }
+            }
+        }
+    }
+}
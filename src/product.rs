@@ -0,0 +1,131 @@
+use crate::{SubsetGenerator, SubsetIter};
+use alloc::vec::Vec;
+
+/// Iterator over the pairs of a subset of one dataset and a subset of another,
+/// created by [`product_subsets`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::{product_subsets, SubsetGenerator};
+/// let a = vec![1, 2];
+/// let b = vec!['x', 'y', 'z'];
+///
+/// let pairs = product_subsets(&SubsetGenerator::new(&a, false), &SubsetGenerator::new(&b, true));
+/// assert_eq!(pairs.count(), 3 * 8);
+/// ```
+pub struct ProductSubsetIter<'a, 'b, A, B> {
+    outer: SubsetIter<'a, A>,
+    inner: SubsetIter<'b, B>,
+    current: Option<Vec<&'a A>>,
+}
+
+/// Returns an iterator over the cartesian product of the subsets of `a` and
+/// the subsets of `b`, as pairs `(subset of a, subset of b)`. For every subset
+/// of `a`, in the order of `iter`, all the subsets of `b` are reported, again
+/// in the order of `iter`. Each generator decides independently whether its
+/// empty set is included, and its constraints apply as usual. The iterator of
+/// `b` is rewound with [`SubsetIter::reset`] for every subset of `a`, so only
+/// the subsets of `a` are cloned.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::{product_subsets, SubsetGenerator};
+///
+/// let a = vec![1, 2];
+/// let b = vec![3];
+/// let sa = SubsetGenerator::new(&a, false);
+/// let sb = SubsetGenerator::new(&b, true);
+/// let mut iter = product_subsets(&sa, &sb);
+///
+/// assert_eq!(iter.next(), Some((vec![&1], vec![])));
+/// assert_eq!(iter.next(), Some((vec![&1], vec![&3])));
+/// assert_eq!(iter.next(), Some((vec![&2], vec![])));
+/// ```
+pub fn product_subsets<'a, 'b, A, B>(
+    a: &SubsetGenerator<'a, A>,
+    b: &SubsetGenerator<'b, B>,
+) -> ProductSubsetIter<'a, 'b, A, B> {
+    ProductSubsetIter {
+        outer: a.iter(),
+        inner: b.iter(),
+        current: None,
+    }
+}
+
+impl<'a, 'b, A, B> Iterator for ProductSubsetIter<'a, 'b, A, B> {
+    type Item = (Vec<&'a A>, Vec<&'b B>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(left) = &self.current {
+                if let Some(right) = self.inner.next() {
+                    return Some((left.clone(), right));
+                }
+            }
+            self.current = Some(self.outer.next()?);
+            self.inner.reset();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let mut fresh = self.inner.clone();
+        fresh.reset();
+        let current = if self.current.is_some() {
+            self.inner.size_hint().1
+        } else {
+            Some(0)
+        };
+        let remaining = self
+            .outer
+            .size_hint()
+            .1
+            .zip(fresh.size_hint().1)
+            .and_then(|(outer, inner)| outer.checked_mul(inner))
+            .zip(current)
+            .and_then(|(rest, current)| rest.checked_add(current));
+        match remaining {
+            Some(remaining) => (remaining, Some(remaining)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{product_subsets, SubsetGenerator};
+
+    #[test]
+    fn product_matches_nested_loops() {
+        let a: Vec<usize> = (0..4).collect();
+        let b: Vec<char> = vec!['p', 'q', 'r'];
+        for &(ea, eb) in &[(false, false), (false, true), (true, false), (true, true)] {
+            let sa = SubsetGenerator::new(&a, ea);
+            let sb = SubsetGenerator::new(&b, eb);
+            let mut expected = Vec::new();
+            for x in sa.iter() {
+                for y in sb.iter() {
+                    expected.push((x.clone(), y));
+                }
+            }
+            let mut iter = product_subsets(&sa, &sb);
+            assert_eq!(iter.size_hint().1, Some(expected.len()));
+            iter.next();
+            assert_eq!(iter.size_hint().1, Some(expected.len() - 1));
+            let mut actual = vec![expected[0].clone()];
+            actual.extend(iter);
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn product_with_empty_side() {
+        let a = vec![1, 2];
+        let empty: Vec<u8> = vec![];
+        let sa = SubsetGenerator::new(&a, true);
+        let sb = SubsetGenerator::new(&empty, false);
+        assert_eq!(product_subsets(&sa, &sb).count(), 0);
+        assert_eq!(product_subsets(&sb, &sa).count(), 0);
+    }
+}
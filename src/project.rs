@@ -0,0 +1,132 @@
+use crate::{SubsetGenerator, SubsetIter};
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// Iterator over all the subsets with a projection applied to their elements,
+/// created by [`SubsetGenerator::project`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![(1, 'a'), (2, 'b')];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// let subsets: Vec<Vec<char>> = sg.project(|&(_, c)| c).collect();
+/// assert_eq!(subsets, vec![vec!['a'], vec!['b'], vec!['a', 'b']]);
+/// ```
+pub struct ProjectedSubsetIter<'a, T, F> {
+    iter: SubsetIter<'a, T>,
+    f: F,
+    buf: Vec<&'a T>,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over all the subsets, where each subset holds the
+    /// values of `f` on its elements instead of references to them, such as
+    /// the weights of `(id, weight)` pairs. The subsets are reported in the
+    /// same order as by `iter`, and only the projected vectors are allocated.
+    ///
+    /// The projection is lazy: `f` is called on every element of every
+    /// subset, so each element is projected once for every subset that
+    /// contains it, half of all subsets. If `f` is expensive, project the
+    /// dataset once up front, and create a generator over the projected values
+    /// instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let items = vec![("apple", 3), ("pear", 5), ("plum", 2)];
+    /// let sg = SubsetGenerator::new(&items, false);
+    /// let mut weights = sg.project(|&(_, weight)| weight);
+    ///
+    /// assert_eq!(weights.next(), Some(vec![3]));
+    /// assert_eq!(weights.next(), Some(vec![5]));
+    /// assert_eq!(weights.next(), Some(vec![3, 5]));
+    /// ```
+    pub fn project<U, F>(&self, f: F) -> ProjectedSubsetIter<'a, T, F>
+    where
+        F: Fn(&T) -> U,
+    {
+        ProjectedSubsetIter {
+            iter: self.iter(),
+            f,
+            buf: Vec::with_capacity(self.data.len()),
+        }
+    }
+}
+
+impl<'a, T, U, F> Iterator for ProjectedSubsetIter<'a, T, F>
+where
+    F: Fn(&T) -> U,
+{
+    type Item = Vec<U>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.iter.next_into(&mut self.buf) {
+            return None;
+        }
+        Some(self.buf.iter().map(|x| (self.f)(x)).collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T, U, F> DoubleEndedIterator for ProjectedSubsetIter<'a, T, F>
+where
+    F: Fn(&T) -> U,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let subset = self.iter.next_back()?;
+        Some(subset.into_iter().map(&self.f).collect())
+    }
+}
+
+impl<'a, T, U, F> ExactSizeIterator for ProjectedSubsetIter<'a, T, F> where F: Fn(&T) -> U {}
+
+impl<'a, T, U, F> FusedIterator for ProjectedSubsetIter<'a, T, F> where F: Fn(&T) -> U {}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+    use std::cell::Cell;
+
+    #[test]
+    fn project_matches_map() {
+        let data: Vec<(usize, i64)> = (0..6).map(|i| (i, i as i64 * 3 - 5)).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset).with_required(&[2]);
+            let expected: Vec<Vec<i64>> = generator
+                .iter()
+                .map(|s| s.into_iter().map(|&(_, w)| w).collect())
+                .collect();
+            let projected: Vec<_> = generator.project(|&(_, w)| w).collect();
+            assert_eq!(projected, expected);
+            assert_eq!(generator.project(|&(_, w)| w).len(), expected.len());
+
+            let mut backward: Vec<_> = generator.project(|&(_, w)| w).rev().collect();
+            backward.reverse();
+            assert_eq!(backward, expected);
+        }
+    }
+
+    #[test]
+    fn project_is_lazy() {
+        let data: Vec<u8> = vec![1, 2, 3, 4];
+        let calls = Cell::new(0);
+        let generator = SubsetGenerator::new(&data, false);
+        let mut iter = generator.project(|&x| {
+            calls.set(calls.get() + 1);
+            x
+        });
+        assert_eq!(calls.get(), 0);
+        iter.nth(2);
+        assert_eq!(calls.get(), 4);
+        iter.by_ref().count();
+        assert_eq!(calls.get(), 4 * 8);
+    }
+}
@@ -0,0 +1,347 @@
+use crate::constraints::FreeElements;
+use crate::SubsetGenerator;
+use alloc::vec::Vec;
+
+/// The verdict of a predicate passed to [`SubsetGenerator::prune_iter`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Prune {
+    /// Report the subset, and continue with its extensions.
+    Keep,
+    /// Do not report the subset, but continue with its extensions.
+    Reject,
+    /// Do not report the subset, and skip all of its extensions.
+    RejectSupersets,
+}
+
+/// Iterator over the subsets accepted by a predicate, skipping the extensions
+/// of rejected subsets, created by [`SubsetGenerator::prune_iter`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::{Prune, SubsetGenerator};
+/// let data = vec![1, 2, 3, 4];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// let iter = sg.prune_iter(|s| if s.len() > 2 { Prune::RejectSupersets } else { Prune::Keep });
+/// assert_eq!(iter.count(), 10);
+/// ```
+pub struct PruneSubsetIter<'a, T, P> {
+    pub(crate) elements: FreeElements<'a, T>,
+    pub(crate) predicate: P,
+    pub(crate) chosen: Vec<usize>,
+    pub(crate) current: Vec<&'a T>,
+    pub(crate) extend: bool,
+    pub(crate) started: bool,
+    pub(crate) with_emptyset: bool,
+    pub(crate) pruned: bool,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over the subsets for which `predicate` returns
+    /// [`Prune::Keep`]. The subsets are visited depth-first: each subset is
+    /// followed by its extensions, the supersets that add elements after its
+    /// last element, before its siblings. This is lexicographic order of the
+    /// included indices, e.g. `{0}, {0, 1}, {0, 1, 2}, {0, 2}, {1}, ...`.
+    ///
+    /// When the predicate returns [`Prune::RejectSupersets`], the whole block
+    /// of extensions is skipped without evaluating the predicate on it. This
+    /// turns a brute force search into branch and bound. The pruning is exact
+    /// if the predicate is monotone, i.e. if every superset of a subset that is
+    /// rejected with its supersets would also be rejected. Supersets that
+    /// include earlier elements are visited elsewhere in the order, and are
+    /// judged by the predicate themselves.
+    ///
+    /// The empty set is the root of the search. It is only passed to the
+    /// predicate, and reported, if the generator was configured to include the
+    /// empty set. The constraints of the generator are respected: the search
+    /// runs over the free elements, in the order set by
+    /// [`with_order`](SubsetGenerator::with_order), every subset contains the
+    /// required elements, and the root is the subset of just the required
+    /// elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::{Prune, SubsetGenerator};
+    ///
+    /// let data = vec![3, 34, 4, 12, 5, 2];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let target = 9;
+    ///
+    /// let solutions: Vec<Vec<&i32>> = sg
+    ///     .prune_iter(|s| {
+    ///         let sum: i32 = s.iter().copied().sum();
+    ///         if sum == target {
+    ///             Prune::Keep
+    ///         } else if sum > target {
+    ///             Prune::RejectSupersets
+    ///         } else {
+    ///             Prune::Reject
+    ///         }
+    ///     })
+    ///     .collect();
+    /// assert_eq!(solutions, vec![vec![&3, &4, &2], vec![&4, &5]]);
+    /// ```
+    pub fn prune_iter<P>(&self, predicate: P) -> PruneSubsetIter<'a, T, P>
+    where
+        P: Fn(&[&T]) -> Prune,
+    {
+        PruneSubsetIter::new(self.free_elements(), predicate, self.includes_base())
+    }
+}
+
+impl<'a, T, P> PruneSubsetIter<'a, T, P> {
+    /// Constructs a depth-first search over the subsets of the free elements,
+    /// positioned at the root (the subset of the required elements).
+    pub(crate) fn new(elements: FreeElements<'a, T>, predicate: P, with_emptyset: bool) -> Self {
+        let len = elements.len();
+        let current = elements.collect(None);
+        PruneSubsetIter {
+            elements,
+            predicate,
+            chosen: Vec::with_capacity(len),
+            current,
+            extend: true,
+            started: false,
+            with_emptyset,
+            pruned: false,
+        }
+    }
+
+    /// Returns the number of subsets that the search has not visited yet, as
+    /// long as no block of extensions has been skipped, or `None` once one
+    /// has. Unlike `len` of the unpruned iterators, this is only an upper
+    /// bound on the number of subsets still to be reported: subsets that are
+    /// rejected are not reported, and later verdicts may still skip blocks of
+    /// subsets. It is exact while the predicate keeps every subset, and serves
+    /// as a best-effort progress signal otherwise. `None` is also returned if
+    /// the number does not fit in a `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::{Prune, SubsetGenerator};
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.prune_iter(|s| if s.len() == 2 { Prune::RejectSupersets } else { Prune::Keep });
+    ///
+    /// assert_eq!(iter.remaining(), Some(7));
+    /// assert_eq!(iter.next(), Some(vec![&1]));
+    /// assert_eq!(iter.remaining(), Some(6));
+    /// iter.next();
+    /// assert_eq!(iter.remaining(), None);
+    /// ```
+    pub fn remaining(&self) -> Option<u64> {
+        if self.pruned {
+            return None;
+        }
+        let len = self.elements.len();
+        let pending = (!self.started && self.with_emptyset) as u64;
+        // The subsets after the current one in depth-first order are its
+        // extensions, and for every chosen index the subsets that replace it
+        // by a later index, followed by any elements after that.
+        let block = |after: usize| match len - after {
+            0 => Some(0),
+            k if k <= 64 => Some(u64::MAX >> (64 - k)),
+            _ => None,
+        };
+        let mut remaining = if self.extend || !self.started {
+            block(self.chosen.last().map_or(0, |&last| last + 1))?
+        } else {
+            0
+        };
+        for &index in &self.chosen {
+            remaining = remaining.checked_add(block(index + 1)?)?;
+        }
+        remaining.checked_add(pending)
+    }
+
+    /// Returns true if the current subset has extensions, so rejecting its
+    /// supersets skips at least one subset.
+    fn has_extensions(&self) -> bool {
+        self.chosen.last().map_or(0, |&last| last + 1) < self.elements.len()
+    }
+
+    /// Moves to the next subset in depth-first order. If `extend` is true, the
+    /// next subset is the first extension of the current one, and otherwise it
+    /// is the next sibling of the current subset (or of its closest ancestor
+    /// that has one). Returns false once the search is exhausted.
+    pub(crate) fn advance(&mut self, extend: bool) -> bool {
+        if !self.advance_chosen(extend) {
+            return false;
+        }
+        // Without constraints the current subset follows the chosen indices,
+        // and was updated along with them.
+        if !self.elements.is_unconstrained() {
+            self.current.clear();
+            let chosen = self.chosen.iter().copied();
+            self.elements.collect_into(chosen, &mut self.current);
+        }
+        true
+    }
+
+    fn advance_chosen(&mut self, extend: bool) -> bool {
+        let len = self.elements.len();
+        if extend {
+            let next = self.chosen.last().map_or(0, |&last| last + 1);
+            if next < len {
+                self.chosen.push(next);
+                self.current.push(self.elements.get(next));
+                return true;
+            }
+        }
+
+        while let Some(last) = self.chosen.pop() {
+            self.current.pop();
+            if last + 1 < len {
+                self.chosen.push(last + 1);
+                self.current.push(self.elements.get(last + 1));
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl<'a, T, P> Iterator for PruneSubsetIter<'a, T, P>
+where
+    P: Fn(&[&T]) -> Prune,
+{
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            if self.with_emptyset {
+                let verdict = (self.predicate)(&self.current);
+                self.extend = verdict != Prune::RejectSupersets;
+                self.pruned |= !self.extend && self.has_extensions();
+                if verdict == Prune::Keep {
+                    return Some(self.current.clone());
+                }
+            }
+        }
+
+        while self.advance(self.extend) {
+            let verdict = (self.predicate)(&self.current);
+            self.extend = verdict != Prune::RejectSupersets;
+            self.pruned |= !self.extend && self.has_extensions();
+            if verdict == Prune::Keep {
+                return Some(self.current.clone());
+            }
+        }
+
+        // The search is exhausted, and the root has no siblings.
+        self.extend = false;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Prune, SubsetGenerator};
+    use std::cell::Cell;
+
+    #[test]
+    fn prune_matches_filter() {
+        let data: Vec<u32> = vec![5, 1, 7, 3, 2, 8, 4];
+        let generator = SubsetGenerator::new(&data, true);
+        let target = 12;
+        let evaluations = Cell::new(0);
+        let mut pruned: Vec<_> = generator
+            .prune_iter(|s| {
+                evaluations.set(evaluations.get() + 1);
+                let sum: u32 = s.iter().copied().sum();
+                if sum > target {
+                    Prune::RejectSupersets
+                } else if sum == target {
+                    Prune::Keep
+                } else {
+                    Prune::Reject
+                }
+            })
+            .collect();
+        let mut filtered: Vec<_> = generator
+            .iter()
+            .filter(|s| s.iter().copied().sum::<u32>() == target)
+            .collect();
+        pruned.sort();
+        filtered.sort();
+        assert_eq!(pruned, filtered);
+        assert!(evaluations.get() < 128);
+    }
+
+    #[test]
+    fn prune_nothing_visits_everything() {
+        let data: Vec<usize> = (0..6).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        let mut visited: Vec<_> = generator.prune_iter(|_| Prune::Keep).collect();
+        assert_eq!(visited.len(), 63);
+        assert_eq!(visited[1], vec![&0, &1]);
+        visited.sort();
+        visited.dedup();
+        assert_eq!(visited.len(), 63);
+    }
+
+    #[test]
+    fn prune_everything() {
+        let data = vec![1, 2, 3];
+        let generator = SubsetGenerator::new(&data, true);
+        let evaluations = Cell::new(0);
+        let mut iter = generator.prune_iter(|_| {
+            evaluations.set(evaluations.get() + 1);
+            Prune::RejectSupersets
+        });
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(evaluations.get(), 1);
+
+        let generator = SubsetGenerator::new(&data, false);
+        assert_eq!(generator.prune_iter(|_| Prune::RejectSupersets).count(), 0);
+        assert_eq!(generator.prune_iter(|_| Prune::Reject).count(), 0);
+    }
+
+    #[test]
+    fn remaining_while_unpruned() {
+        let data: Vec<usize> = (0..7).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let mut iter = generator.prune_iter(|_| Prune::Keep);
+            let mut expected = generator.iter().len() as u64;
+            assert_eq!(iter.remaining(), Some(expected));
+            while iter.next().is_some() {
+                expected -= 1;
+                assert_eq!(iter.remaining(), Some(expected));
+            }
+            assert_eq!(expected, 0);
+        }
+
+        // Rejecting the supersets of a full set skips nothing.
+        let generator = SubsetGenerator::new(&data, false);
+        let mut iter = generator.prune_iter(|s| {
+            if s.contains(&&6) {
+                Prune::RejectSupersets
+            } else {
+                Prune::Reject
+            }
+        });
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.remaining(), Some(0));
+
+        // Exactly 2^64 - 1 subsets still fit in a u64.
+        let wide: Vec<usize> = (0..64).collect();
+        let generator = SubsetGenerator::new(&wide, false);
+        let mut iter = generator.prune_iter(|_| Prune::Keep);
+        assert_eq!(iter.remaining(), Some(u64::MAX));
+        iter.next();
+        assert_eq!(iter.remaining(), Some(u64::MAX - 1));
+        let generator = SubsetGenerator::new(&wide, true);
+        assert_eq!(generator.prune_iter(|_| Prune::Keep).remaining(), None);
+
+        let big: Vec<usize> = (0..80).collect();
+        let generator = SubsetGenerator::new(&big, false);
+        assert_eq!(generator.prune_iter(|_| Prune::Keep).remaining(), None);
+    }
+}
@@ -0,0 +1,175 @@
+//! Ranking and unranking of subsets. The rank of a subset is its index in
+//! `[0, 2^n)`, which is the integer whose binary representation is the bitmask
+//! of the subset: bit `i` of the BitVec (the `2^i` place) is set if and only if
+//! the `i`-th element of the dataset is included. This matches the order in
+//! which [`SubsetIter`](crate::SubsetIter) reports the subsets, so a range of
+//! ranks corresponds to a contiguous part of the enumeration. Work can thus be
+//! distributed by index range, with each worker reconstructing its subsets
+//! through [`unrank`].
+//!
+//! With the `big` feature, [`rank_big`] and [`unrank_big`] do the same with
+//! `BigUint` indices, so they work for datasets of any size.
+use bit_vec::BitVec;
+#[cfg(feature = "big")]
+use num_bigint::BigUint;
+
+/// Returns the rank of the subset described by `mask`.
+///
+/// # Panics
+///
+/// Panics if a bit at position 64 or higher is set, as the rank would not fit
+/// in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bit_vec::BitVec;
+/// use subset_generator::rank::rank;
+///
+/// let mask = BitVec::from_fn(4, |i| i == 0 || i == 2);
+/// assert_eq!(rank(&mask), 5);
+/// ```
+pub fn rank(mask: &BitVec) -> u64 {
+    let mut index = 0;
+    for i in 0..mask.len() {
+        if mask[i] {
+            assert!(i < 64, "the rank of the subset does not fit in a u64");
+            index |= 1 << i;
+        }
+    }
+    index
+}
+
+/// Returns the mask of length `n` of the subset with the given rank.
+///
+/// # Panics
+///
+/// Panics if `index >= 2^n`.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::rank::{rank, unrank};
+///
+/// let mask = unrank(5, 4);
+/// assert!(mask.eq_vec(&[true, false, true, false]));
+/// assert_eq!(rank(&mask), 5);
+/// ```
+pub fn unrank(index: u64, n: usize) -> BitVec {
+    assert!(
+        n >= 64 || index >> n == 0,
+        "the index is out of range for a dataset of {} elements",
+        n
+    );
+    BitVec::from_fn(n, |i| i < 64 && (index >> i) & 1 == 1)
+}
+
+/// Returns the rank of the subset described by `mask` as a `BigUint`, which
+/// works for masks of any length. Only available with the `big` feature.
+///
+/// # Examples
+///
+/// ```
+/// use bit_vec::BitVec;
+/// use num_bigint::BigUint;
+/// use subset_generator::rank::rank_big;
+///
+/// let mask = BitVec::from_fn(80, |i| i == 0 || i == 70);
+/// assert_eq!(rank_big(&mask), (BigUint::from(1u8) << 70) + 1u8);
+/// ```
+#[cfg(feature = "big")]
+pub fn rank_big(mask: &BitVec) -> BigUint {
+    let mut index = BigUint::default();
+    for i in 0..mask.len() {
+        if mask[i] {
+            index.set_bit(i as u64, true);
+        }
+    }
+    index
+}
+
+/// Returns the mask of length `n` of the subset with the given `BigUint`
+/// rank. Only available with the `big` feature.
+///
+/// # Panics
+///
+/// Panics if `index >= 2^n`.
+///
+/// # Examples
+///
+/// ```
+/// use num_bigint::BigUint;
+/// use subset_generator::rank::{rank_big, unrank_big};
+///
+/// let index = BigUint::from(1u8) << 75;
+/// let mask = unrank_big(&index, 80);
+/// assert!(mask[75]);
+/// assert_eq!(rank_big(&mask), index);
+/// ```
+#[cfg(feature = "big")]
+pub fn unrank_big(index: &BigUint, n: usize) -> BitVec {
+    assert!(
+        index.bits() <= n as u64,
+        "the index is out of range for a dataset of {} elements",
+        n
+    );
+    BitVec::from_fn(n, |i| index.bit(i as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for n in 0..10 {
+            for index in 0..(1 << n) {
+                let mask = unrank(index, n);
+                assert_eq!(mask.len(), n);
+                assert_eq!(rank(&mask), index);
+                assert_eq!(unrank(rank(&mask), n), mask);
+            }
+        }
+    }
+
+    #[test]
+    fn matches_iteration_order() {
+        let data: Vec<usize> = (0..5).collect();
+        let generator = crate::SubsetGenerator::new(&data, true);
+        for (index, subset) in generator.iter().enumerate() {
+            let mask = unrank(index as u64, data.len());
+            let expected: Vec<usize> = (0..5).filter(|&i| mask[i]).collect();
+            assert_eq!(subset.into_iter().copied().collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn large_masks() {
+        let mut mask = BitVec::from_elem(100, false);
+        mask.set(63, true);
+        assert_eq!(rank(&mask), 1 << 63);
+        assert_eq!(unrank(u64::MAX, 100).count_ones(), 64);
+    }
+
+    #[cfg(feature = "big")]
+    #[test]
+    fn big_round_trip() {
+        let n = 80;
+        let mut index = BigUint::from(0x1234_5678_9abc_def0u64) << 15;
+        index += 0xfedcu32;
+        let mask = unrank_big(&index, n);
+        assert_eq!(mask.len(), n);
+        assert_eq!(rank_big(&mask), index);
+        assert_eq!(unrank_big(&rank_big(&mask), n), mask);
+
+        let small = unrank(12345, n);
+        assert_eq!(rank_big(&small), BigUint::from(12345u32));
+        assert_eq!(unrank_big(&BigUint::from(12345u32), n), small);
+    }
+
+    #[test]
+    #[should_panic]
+    fn unrank_out_of_range() {
+        unrank(8, 3);
+    }
+}
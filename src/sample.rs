@@ -0,0 +1,371 @@
+use crate::mask::Mask;
+use crate::{SubsetGenerator, SubsetIter};
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use bit_vec::BitVec;
+use core::convert::TryFrom;
+use rand::seq::index;
+use rand::Rng;
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Draws `k` subsets uniformly at random, with replacement, from all the
+    /// subsets the generator would report. Each draw is a random mask of *n*
+    /// bits, so no enumeration takes place and the dataset may be arbitrarily
+    /// large. The all-zero mask is a valid draw if (and only if) the generator
+    /// was configured to include the empty set; otherwise it is rejected and
+    /// drawn again. Use [`sample_distinct`](Self::sample_distinct) to draw
+    /// without replacement.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > 0` but the generator has no subsets at all, which is the
+    /// case for an empty dataset without the empty set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data: Vec<u32> = (0..100).collect();
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut rng = StdRng::seed_from_u64(7);
+    ///
+    /// let samples = sg.sample(&mut rng, 10);
+    /// assert_eq!(samples.len(), 10);
+    /// ```
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R, k: usize) -> Vec<Vec<&'a T>> {
+        assert!(
+            k == 0 || self.free_len() > 0 || self.includes_base(),
+            "the generator has no subsets to sample from"
+        );
+        let iter = self.iter();
+        (0..k)
+            .map(|_| select(&iter, &self.random_mask(rng)))
+            .collect()
+    }
+
+    /// Draws `k` distinct subsets uniformly at random, without replacement,
+    /// from all the subsets the generator would report. If the number of
+    /// subsets fits in a `usize`, `k` distinct indices are drawn directly;
+    /// otherwise random masks are drawn, rejecting the ones seen before. As for
+    /// [`sample`](Self::sample), the all-zero mask is only a valid draw if the
+    /// generator was configured to include the empty set. The subsets are
+    /// returned in the order they were drawn.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` exceeds the number of subsets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, true);
+    /// let mut rng = StdRng::seed_from_u64(7);
+    ///
+    /// let mut samples = sg.sample_distinct(&mut rng, 8);
+    /// let mut all: Vec<_> = sg.iter().collect();
+    /// samples.sort();
+    /// all.sort();
+    /// assert_eq!(samples, all);
+    /// ```
+    pub fn sample_distinct<R: Rng + ?Sized>(&self, rng: &mut R, k: usize) -> Vec<Vec<&'a T>> {
+        let len = self.free_len();
+        let skipped = !self.includes_base() as u64;
+        let total = if len < 64 {
+            usize::try_from((1u64 << len) - skipped).ok()
+        } else {
+            None
+        };
+        let iter = self.iter();
+
+        if let Some(total) = total {
+            assert!(
+                k <= total,
+                "cannot draw {} distinct subsets out of {}",
+                k,
+                total
+            );
+            return index::sample(rng, total, k)
+                .into_iter()
+                .map(|i| select(&iter, &Mask::from_index(i as u64 + skipped, len)))
+                .collect();
+        }
+
+        let mut seen = BTreeSet::new();
+        let mut samples = Vec::with_capacity(k);
+        while samples.len() < k {
+            let mask = self.random_mask(rng);
+            if seen.insert(mask.clone()) {
+                samples.push(select(&iter, &mask));
+            }
+        }
+        samples
+    }
+
+    /// Draws `count` subsets of exactly `size` elements uniformly at random,
+    /// with replacement, from the `C(n, size)` subsets of that size. Each draw
+    /// picks `size` distinct indices with a partial Fisher–Yates shuffle, so
+    /// it takes *O(size)* time regardless of how large `C(n, size)` is. The
+    /// elements of each subset are listed in the order of the dataset. Like
+    /// [`combinations`](Self::combinations), this ignores whether the generator
+    /// includes the empty set, so `size == 0` yields empty subsets, and
+    /// respects its constraints: `size` counts the required elements too, and
+    /// only the free elements are drawn.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` exceeds the number of elements that are not
+    /// forbidden, or is less than the number of required elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data: Vec<u32> = (0..1000).collect();
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut rng = StdRng::seed_from_u64(7);
+    ///
+    /// for subset in sg.sample_k(&mut rng, 5, 10) {
+    ///     assert_eq!(subset.len(), 5);
+    /// }
+    /// ```
+    pub fn sample_k<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        size: usize,
+        count: usize,
+    ) -> Vec<Vec<&'a T>> {
+        let elements = self.free_elements();
+        let (required, len) = (elements.required_len(), elements.len());
+        assert!(
+            size <= required + len,
+            "cannot draw subsets of {} elements out of {}",
+            size,
+            required + len
+        );
+        assert!(
+            size >= required,
+            "cannot draw subsets of {} elements with {} required",
+            size,
+            required
+        );
+        (0..count)
+            .map(|_| {
+                let mut bits = index::sample(rng, len, size - required).into_vec();
+                bits.sort_unstable();
+                elements.collect(bits)
+            })
+            .collect()
+    }
+
+    /// Draws a uniformly random mask over the free elements, rejecting the
+    /// all-zero mask unless the base subset is reported.
+    fn random_mask<R: Rng + ?Sized>(&self, rng: &mut R) -> Mask {
+        let len = self.free_len();
+        loop {
+            let mask = if len <= 64 {
+                let bits = if len == 0 {
+                    0
+                } else {
+                    rng.gen::<u64>() >> (64 - len)
+                };
+                Mask::Small { bits, len }
+            } else {
+                Mask::Large(BitVec::from_fn(len, |_| rng.gen()))
+            };
+            if self.includes_base() || mask.lowest_set().is_some() {
+                return mask;
+            }
+        }
+    }
+}
+
+/// Collects the subset selected by `mask` over the free elements of `iter`.
+fn select<'a, T>(iter: &SubsetIter<'a, T>, mask: &Mask) -> Vec<&'a T> {
+    let mut subset = Vec::new();
+    iter.select_into(Some(mask), &mut subset);
+    subset
+}
+
+/// Picks `k` items uniformly at random from `iter` in a single pass, using
+/// reservoir sampling (Algorithm R). Every `k`-element selection of the items
+/// is equally likely, and only the `k` items of the reservoir are kept. Unlike
+/// [`SubsetGenerator::sample`], this works on any stream of subsets, such as
+/// the output of `prune_iter` or a filtered iterator, whose size need not be
+/// known in advance. If the stream has at most `k` items, all of them are
+/// returned, in order; otherwise the order of the sample is unspecified.
+/// Only available with the `rand` feature.
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use subset_generator::{reservoir_sample, SubsetGenerator};
+///
+/// let data: Vec<u32> = (0..10).collect();
+/// let sg = SubsetGenerator::new(&data, false);
+/// let mut rng = StdRng::seed_from_u64(7);
+///
+/// let pairs = sg.iter().filter(|s| s.len() == 2);
+/// let sample = reservoir_sample(pairs, 3, &mut rng);
+/// assert_eq!(sample.len(), 3);
+/// assert!(sample.iter().all(|s| s.len() == 2));
+/// ```
+pub fn reservoir_sample<I, R>(iter: I, k: usize, rng: &mut R) -> Vec<I::Item>
+where
+    I: Iterator,
+    R: Rng + ?Sized,
+{
+    let mut reservoir = Vec::with_capacity(k);
+    for (seen, item) in iter.enumerate() {
+        if seen < k {
+            reservoir.push(item);
+        } else {
+            let slot = rng.gen_range(0..=seen);
+            if slot < k {
+                reservoir[slot] = item;
+            }
+        }
+    }
+    reservoir
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{reservoir_sample, SubsetGenerator, SubsetIterExt};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::HashSet;
+
+    #[test]
+    fn samples_are_valid_subsets() {
+        let data: Vec<usize> = (0..200).collect();
+        let generator = SubsetGenerator::new(&data, false)
+            .with_required(&[3])
+            .with_forbidden(&[5]);
+        let mut rng = StdRng::seed_from_u64(1);
+        for subset in generator.sample(&mut rng, 20) {
+            assert!(subset.contains(&&3) && !subset.contains(&&5));
+            assert!(subset.windows(2).all(|w| w[0] < w[1]));
+        }
+    }
+
+    #[test]
+    fn sample_without_emptyset() {
+        let data = vec![1];
+        let generator = SubsetGenerator::new(&data, false);
+        let mut rng = StdRng::seed_from_u64(2);
+        assert!(generator
+            .sample(&mut rng, 50)
+            .iter()
+            .all(|s| s == &vec![&1]));
+    }
+
+    #[test]
+    fn sample_is_roughly_uniform() {
+        let data = vec![1, 2, 3];
+        let generator = SubsetGenerator::new(&data, true);
+        let mut rng = StdRng::seed_from_u64(3);
+        let samples = generator.sample(&mut rng, 8000);
+        for subset in generator.iter() {
+            let hits = samples.iter().filter(|s| **s == subset).count();
+            assert!((800..1200).contains(&hits), "{:?}: {}", subset, hits);
+        }
+    }
+
+    #[test]
+    fn distinct_samples() {
+        let data: Vec<usize> = (0..4).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        let mut rng = StdRng::seed_from_u64(4);
+        let samples = generator.sample_distinct(&mut rng, 15);
+        let unique: HashSet<_> = samples.iter().collect();
+        assert_eq!(unique.len(), 15);
+        assert!(!unique.contains(&Vec::new()));
+
+        let data: Vec<usize> = (0..100).collect();
+        let generator = SubsetGenerator::new(&data, true);
+        let samples = generator.sample_distinct(&mut rng, 100);
+        assert_eq!(samples.iter().collect::<HashSet<_>>().len(), 100);
+    }
+
+    #[test]
+    fn fixed_size_samples() {
+        let data: Vec<usize> = (0..6).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        let mut rng = StdRng::seed_from_u64(6);
+        let samples = generator.sample_k(&mut rng, 3, 4000);
+        for subset in &samples {
+            assert_eq!(subset.len(), 3);
+            assert!(subset.windows(2).all(|w| w[0] < w[1]));
+        }
+        // Each of the C(6, 3) = 20 subsets should be drawn about 200 times.
+        for subset in generator.combinations(3) {
+            let hits = samples.iter().filter(|s| **s == subset).count();
+            assert!((120..280).contains(&hits), "{:?}: {}", subset, hits);
+        }
+
+        assert!(generator.sample_k(&mut rng, 0, 2).iter().all(Vec::is_empty));
+        assert_eq!(generator.sample_k(&mut rng, 6, 1)[0].len(), 6);
+
+        let constrained = generator.with_required(&[4]).with_forbidden(&[1]);
+        let samples = constrained.sample_k(&mut rng, 3, 200);
+        let valid: Vec<_> = constrained.combinations(3).collect();
+        assert!(samples.iter().all(|s| valid.contains(s)));
+    }
+
+    #[test]
+    #[should_panic(expected = "with 1 required")]
+    fn fixed_size_too_small() {
+        let data = vec![1, 2];
+        let generator = SubsetGenerator::new(&data, true).with_required(&[0]);
+        generator.sample_k(&mut StdRng::seed_from_u64(7), 0, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn fixed_size_too_large() {
+        let data = vec![1, 2];
+        let generator = SubsetGenerator::new(&data, true);
+        generator.sample_k(&mut StdRng::seed_from_u64(7), 3, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_many_distinct_samples() {
+        let data = vec![1, 2];
+        let generator = SubsetGenerator::new(&data, true);
+        generator.sample_distinct(&mut StdRng::seed_from_u64(5), 5);
+    }
+
+    #[test]
+    fn reservoir_is_roughly_uniform() {
+        let data: Vec<u32> = (0..4).collect();
+        let generator = SubsetGenerator::new(&data, true);
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut counts = [0u32; 16];
+        for _ in 0..4000 {
+            for subset in generator.iter().reservoir_sample(2, &mut rng) {
+                let index: usize = subset.iter().map(|&&x| 1 << x).sum();
+                counts[index] += 1;
+            }
+        }
+        // Each subset is expected 500 times.
+        assert!(counts.iter().all(|&c| (400..600).contains(&c)));
+
+        let short = reservoir_sample(generator.iter().take(3), 5, &mut rng);
+        assert_eq!(short, generator.iter().take(3).collect::<Vec<_>>());
+        assert!(reservoir_sample(generator.iter(), 0, &mut rng).is_empty());
+    }
+}
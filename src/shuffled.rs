@@ -0,0 +1,187 @@
+use crate::mask::Mask;
+use crate::{SubsetGenerator, SubsetIter};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::iter::FusedIterator;
+
+/// Iterator over all the subsets in a pseudo-random order, created by
+/// [`SubsetGenerator::shuffled_iter`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// let mut subsets: Vec<_> = sg.shuffled_iter(7).collect();
+/// subsets.sort();
+/// subsets.dedup();
+/// assert_eq!(subsets.len(), 7);
+/// ```
+pub struct ShuffledSubsetIter<'a, T> {
+    iter: SubsetIter<'a, T>,
+    keys: [u64; 4],
+    position: u64,
+    remaining: u128,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over all the subsets in a pseudo-random order that
+    /// is determined by `seed`, so the same seed always gives the same order.
+    /// The indices `0, 1, 2, ...` are mapped to subset indices by a keyed
+    /// bijection of `[0, 2^n)`, built from multiplications by odd constants
+    /// and xor-shifts, so every subset is reported exactly once while only
+    /// *O(n)* memory is used. This spreads the first subsets over the whole
+    /// powerset, which gives randomized search heuristics better coverage
+    /// when they are stopped early than the linear order of `iter`.
+    ///
+    /// The empty set is only reported if the generator includes it, and the
+    /// constraints of the generator are respected. The order is meant for
+    /// heuristics, and is not suitable for cryptographic purposes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dataset has more than 64 elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3, 4];
+    /// let sg = SubsetGenerator::new(&data, true);
+    ///
+    /// let first: Vec<_> = sg.shuffled_iter(42).take(5).collect();
+    /// assert_eq!(first, sg.shuffled_iter(42).take(5).collect::<Vec<_>>());
+    /// assert_eq!(sg.shuffled_iter(42).count(), 16);
+    /// ```
+    pub fn shuffled_iter(&self, seed: u64) -> ShuffledSubsetIter<'a, T> {
+        let len = self.free_len();
+        assert!(
+            len <= 64,
+            "shuffled_iter supports datasets of at most 64 elements"
+        );
+        let mut state = seed;
+        let mut keys = [0; 4];
+        for key in keys.iter_mut() {
+            *key = splitmix64(&mut state);
+        }
+        // The multipliers must be odd to be invertible modulo 2^n.
+        keys[0] |= 1;
+        keys[2] |= 1;
+        ShuffledSubsetIter {
+            iter: self.iter(),
+            keys,
+            position: 0,
+            remaining: 1u128 << len,
+        }
+    }
+}
+
+impl<'a, T> ShuffledSubsetIter<'a, T> {
+    /// Maps a position to a subset index. Every step is a bijection of the
+    /// integers modulo `2^n`: multiplying by an odd number, adding a number,
+    /// and xoring a number with a right shift of itself.
+    fn permute(&self, position: u64) -> u64 {
+        let bits = self.iter.set.len();
+        let mask = if bits == 64 {
+            u64::MAX
+        } else {
+            (1 << bits) - 1
+        };
+        let mut x = position;
+        x = x.wrapping_mul(self.keys[0]).wrapping_add(self.keys[1]) & mask;
+        x ^= x >> (bits / 2 + 1);
+        x = x.wrapping_mul(self.keys[2]).wrapping_add(self.keys[3]) & mask;
+        x ^= x >> (bits / 3 + 1);
+        x
+    }
+}
+
+impl<'a, T> Iterator for ShuffledSubsetIter<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.remaining > 0 {
+            let index = self.permute(self.position);
+            self.position = self.position.wrapping_add(1);
+            self.remaining -= 1;
+
+            let mut subset = Vec::new();
+            if index == 0 {
+                if !self.iter.emptyset {
+                    continue;
+                }
+                self.iter.select_into(None, &mut subset);
+            } else {
+                let mask = Mask::from_index(index, self.iter.set.len());
+                self.iter.select_into(Some(&mask), &mut subset);
+            }
+            return Some(subset);
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // The base subset may still be skipped.
+        let upper = usize::try_from(self.remaining).ok();
+        let lower = self.remaining.saturating_sub(!self.iter.emptyset as u128);
+        (usize::try_from(lower).unwrap_or(usize::MAX), upper)
+    }
+}
+
+impl<'a, T> FusedIterator for ShuffledSubsetIter<'a, T> {}
+
+/// Advances a SplitMix64 generator and returns its next output, which is used
+/// to derive well-mixed keys from any seed.
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+
+    #[test]
+    fn shuffled_is_permutation() {
+        for n in 0..11 {
+            let data: Vec<usize> = (0..n).collect();
+            for &with_emptyset in &[false, true] {
+                let generator = SubsetGenerator::new(&data, with_emptyset);
+                let mut expected: Vec<_> = generator.iter().collect();
+                expected.sort();
+                for seed in 0..4 {
+                    let mut shuffled: Vec<_> = generator.shuffled_iter(seed).collect();
+                    shuffled.sort();
+                    assert_eq!(shuffled, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shuffled_order_depends_on_seed() {
+        let data: Vec<usize> = (0..12).collect();
+        let generator = SubsetGenerator::new(&data, false).with_required(&[3]);
+        let a: Vec<_> = generator.shuffled_iter(1).take(20).collect();
+        let b: Vec<_> = generator.shuffled_iter(2).take(20).collect();
+        assert_ne!(a, b);
+        assert_ne!(a, generator.iter().take(20).collect::<Vec<_>>());
+        assert!(a.iter().all(|s| s.contains(&&3)));
+        assert_eq!(generator.shuffled_iter(1).count(), 1 << 11);
+    }
+
+    #[test]
+    fn shuffled_full_width() {
+        let data: Vec<u8> = vec![0; 64];
+        let generator = SubsetGenerator::new(&data, false);
+        let mut iter = generator.shuffled_iter(5);
+        assert_eq!(iter.size_hint().1, None);
+        assert!(iter.next().is_some());
+    }
+}
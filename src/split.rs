@@ -0,0 +1,449 @@
+use crate::mask::Mask;
+use crate::{SubsetGenerator, SubsetIter};
+use alloc::vec::Vec;
+use bit_vec::BitVec;
+use core::iter::FusedIterator;
+
+/// Iterator over a share of every cardinality layer of the subsets, created by
+/// [`SubsetGenerator::split_by_layer`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// let first = sg.split_by_layer(2).remove(0);
+/// let sizes: Vec<usize> = first.map(|s| s.len()).collect();
+/// assert_eq!(sizes, vec![1, 1, 2, 2, 3]);
+/// ```
+pub struct LayerSplitIter<'a, T> {
+    iter: SubsetIter<'a, T>,
+    popcount: usize,
+    chunk: usize,
+    chunks: usize,
+    // The number of masks in the current layer, or `None` if it does not fit
+    // in a `u128`, and the position in the layer of the next mask to report.
+    layer: Option<u128>,
+    position: u128,
+    // The number of masks to step over before reporting one, for layers that
+    // are too large to count.
+    skip: usize,
+    #[cfg(test)]
+    visited: usize,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Splits the enumeration into `chunks` iterators, which cover contiguous
+    /// and non-overlapping ranges of the subset indices (see
+    /// [`rank`](crate::rank)). Together they report every subset exactly once,
+    /// and the empty set (if configured) is reported by the first non-empty
+    /// chunk only. The ranges are as equal in size as possible, and chaining
+    /// the iterators in order reproduces `iter`. If there are more chunks than
+    /// subsets, the surplus iterators are empty.
+    ///
+    /// This is the building block for processing the subsets on several
+    /// threads, for instance by moving each iterator into its own
+    /// `std::thread::scope` thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunks == 0`, or if the dataset has more than 64 elements.
+    /// With the `big` feature, `split_big` supports datasets of any size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, true);
+    /// let chunks = sg.split(2);
+    ///
+    /// let sizes: Vec<usize> = chunks.into_iter().map(|c| c.count()).collect();
+    /// assert_eq!(sizes, vec![4, 4]);
+    /// ```
+    pub fn split(&self, chunks: usize) -> Vec<SubsetIter<'a, T>> {
+        assert!(chunks > 0, "cannot split the subsets into zero chunks");
+        let len = self.free_len();
+        assert!(len <= 64, "split supports datasets of at most 64 elements");
+
+        let total = 1u128 << len;
+        (0..chunks as u128)
+            .map(|i| self.range(total * i / chunks as u128, total * (i + 1) / chunks as u128))
+            .collect()
+    }
+
+    /// Splits the enumeration into `chunks` iterators that share every
+    /// cardinality layer evenly: within each layer, ordered like `iter`, the
+    /// `i`-th chunk reports the subsets at positions `i`, `i + chunks`,
+    /// `i + 2 * chunks`, and so on. Together the chunks report every subset
+    /// exactly once, and each chunk reports its subsets by increasing size,
+    /// like [`by_popcount`](Self::by_popcount). The empty set (if configured)
+    /// is reported by the first chunk.
+    ///
+    /// The contiguous index ranges of [`split`](Self::split) hold equally many
+    /// subsets, but not equally large ones: the last chunks cover the masks
+    /// with the highest bits set, and so hold larger subsets on average. Here,
+    /// the number of subsets of every size differs by at most one between the
+    /// chunks, so prefer this split when the cost of processing a subset grows
+    /// with its size, and `split` otherwise. Each chunk computes the masks of
+    /// its share directly from their positions in the layer, so it does not
+    /// step through the masks of the other chunks. Chaining the chunks no
+    /// longer reproduces `iter`, though.
+    ///
+    /// The constraints of the generator are respected, in which case the
+    /// layers are formed by the number of free elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunks == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3, 4];
+    /// let sg = SubsetGenerator::new(&data, false);
+    ///
+    /// let sizes: Vec<Vec<usize>> = sg
+    ///     .split_by_layer(2)
+    ///     .into_iter()
+    ///     .map(|chunk| chunk.map(|s| s.len()).collect())
+    ///     .collect();
+    /// assert_eq!(sizes[0], vec![1, 1, 2, 2, 2, 3, 3, 4]);
+    /// assert_eq!(sizes[1], vec![1, 1, 2, 2, 2, 3, 3]);
+    /// ```
+    pub fn split_by_layer(&self, chunks: usize) -> Vec<LayerSplitIter<'a, T>> {
+        assert!(chunks > 0, "cannot split the subsets into zero chunks");
+        (0..chunks)
+            .map(|chunk| LayerSplitIter {
+                iter: self.iter(),
+                popcount: 0,
+                chunk,
+                chunks,
+                layer: Some(self.includes_base() as u128),
+                position: chunk as u128,
+                skip: chunk,
+                #[cfg(test)]
+                visited: 0,
+            })
+            .collect()
+    }
+
+    /// Returns an iterator over the subsets with an index in `[start, end)`.
+    /// The empty set is only reported if the range contains index 0. The
+    /// bounds are `u128`, so that `end` can be `2^64` for 64 free elements.
+    pub(crate) fn range(&self, start: u128, end: u128) -> SubsetIter<'a, T> {
+        let len = self.free_len();
+        let empty = start >= end;
+
+        // The front cursor holds the last reported mask, and the back cursor
+        // the last mask to report.
+        let first = start.saturating_sub(1);
+        let last = if empty { first } else { end - 1 };
+        self.range_of(
+            Mask::from_index(first as u64, len),
+            Mask::from_index(last as u64, len),
+            start == 0 && !empty,
+        )
+    }
+
+    /// Returns an iterator whose front cursor is `set` and whose back cursor is
+    /// `back`. The base subset is only reported if `with_base` is set.
+    pub(crate) fn range_of(&self, set: Mask, back: Mask, with_base: bool) -> SubsetIter<'a, T> {
+        SubsetIter {
+            data: self.data,
+            set,
+            back,
+            with_emptyset: self.includes_base() && with_base,
+            emptyset: self.includes_base(),
+            constraints: self.constraints.clone(),
+            exhausted: false,
+            reported: false,
+        }
+    }
+}
+
+impl<'a, T> LayerSplitIter<'a, T> {
+    /// Returns the next mask of the current layer to report, or `None` once
+    /// the share of the chunk in the layer is exhausted.
+    fn next_mask(&mut self) -> Option<Mask> {
+        let len = self.iter.set.len();
+        let size = match self.layer {
+            Some(size) => size,
+            None => return self.step(),
+        };
+        if self.position >= size {
+            return None;
+        }
+        let position = self.position;
+        self.position = position.saturating_add(self.chunks as u128);
+        self.visit();
+        let set = unrank_layer(position, self.popcount, len, size);
+        Some(Mask::from_bitvec(set))
+    }
+
+    /// Steps the cursor through the masks of a layer that is too large to
+    /// count, and returns every `chunks`-th one.
+    fn step(&mut self) -> Option<Mask> {
+        loop {
+            let iter = &mut self.iter;
+            if iter.exhausted
+                || !iter.set.less_than(&iter.back)
+                || !iter.set.next_with_ones(self.popcount)
+                || iter.back.less_than(&iter.set)
+            {
+                iter.exhausted = true;
+                return None;
+            }
+            self.visit();
+            if self.skip == 0 {
+                self.skip = self.chunks - 1;
+                return Some(self.iter.set.clone());
+            }
+            self.skip -= 1;
+        }
+    }
+
+    /// Moves on to the layer of the next larger subsets.
+    fn next_layer(&mut self) {
+        self.popcount += 1;
+        self.layer = binomial(self.iter.set.len(), self.popcount);
+        self.position = self.chunk as u128;
+        self.skip = self.chunk;
+        self.iter.reset();
+    }
+
+    /// Counts a mask that the cursor moved to, so that the tests can bound
+    /// the work of a chunk.
+    fn visit(&mut self) {
+        #[cfg(test)]
+        {
+            self.visited += 1;
+        }
+    }
+}
+
+impl<'a, T> Iterator for LayerSplitIter<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.popcount <= self.iter.set.len() {
+            if let Some(mask) = self.next_mask() {
+                let mut result = Vec::new();
+                self.iter.select_into(Some(&mask), &mut result);
+                return Some(result);
+            }
+            self.next_layer();
+        }
+        None
+    }
+}
+
+/// Returns the binomial coefficient `C(n, k)`, or `None` if it does not fit in
+/// a `u128`.
+fn binomial(n: usize, k: usize) -> Option<u128> {
+    let k = k.min(n.saturating_sub(k));
+    (0..k).try_fold(1u128, |acc, i| scale(acc, (n - i) as u128, i as u128 + 1))
+}
+
+/// Returns `value * numerator / denominator`, which must be an integer, or
+/// `None` if it does not fit in a `u128`. The product itself may overflow.
+fn scale(value: u128, numerator: u128, denominator: u128) -> Option<u128> {
+    let (quotient, remainder) = (value / denominator, value % denominator);
+    quotient
+        .checked_mul(numerator)?
+        .checked_add(remainder * numerator / denominator)
+}
+
+/// Returns the mask of `len` bits with `ones` set bits at `position` among all
+/// such masks in increasing order, of which there are `size = C(len, ones)`.
+/// The bits are placed from the top: bit `c` is set if the position is at
+/// least the number `C(c, ones)` of masks that only use the bits below it.
+fn unrank_layer(mut position: u128, mut ones: usize, len: usize, size: u128) -> BitVec {
+    let mut set = BitVec::from_elem(len, false);
+    // The number of masks with `ones` set bits below bit `c + 1`.
+    let mut above = size;
+    for c in (0..len).rev() {
+        if ones == 0 {
+            break;
+        }
+        // Shrinking `C(c + 1, ones)` to `C(c, ones)` cannot overflow.
+        let below = scale(above, (c + 1 - ones) as u128, c as u128 + 1).unwrap();
+        if position >= below {
+            set.set(c, true);
+            position -= below;
+            above -= below;
+            ones -= 1;
+        } else {
+            above = below;
+        }
+    }
+    set
+}
+
+impl<'a, T> FusedIterator for LayerSplitIter<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{binomial, unrank_layer};
+    use crate::mask::Mask;
+    use crate::SubsetGenerator;
+
+    #[test]
+    fn chunks_cover_everything_once() {
+        let data: Vec<usize> = (0..7).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let expected: Vec<_> = generator.iter().collect();
+            for chunks in 1..20 {
+                let split = generator.split(chunks);
+                assert_eq!(split.len(), chunks);
+                let concatenated: Vec<_> = split.into_iter().flatten().collect();
+                assert_eq!(concatenated, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn more_chunks_than_subsets() {
+        let data = vec![1, 2];
+        let generator = SubsetGenerator::new(&data, true);
+        let sizes: Vec<usize> = generator.split(6).into_iter().map(|c| c.len()).collect();
+        assert_eq!(sizes.iter().sum::<usize>(), 4);
+        assert!(sizes.iter().all(|&size| size <= 1));
+
+        let empty: Vec<i32> = vec![];
+        let generator = SubsetGenerator::new(&empty, true);
+        let subsets: Vec<_> = generator.split(3).into_iter().flatten().collect();
+        assert_eq!(subsets, vec![Vec::<&i32>::new()]);
+    }
+
+    #[test]
+    fn sixty_four_elements() {
+        let data: Vec<usize> = (0..64).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let mut split = generator.split(4);
+            let sizes: Vec<_> = split.iter().map(|c| c.size_hint()).collect();
+            let quarter = 1 << 62;
+            let first = quarter - !with_emptyset as usize;
+            assert_eq!(sizes[0], (first, Some(first)));
+            assert!(sizes[1..]
+                .iter()
+                .all(|&size| size == (quarter, Some(quarter))));
+
+            let expected = if with_emptyset { vec![] } else { vec![&0] };
+            assert_eq!(split[0].next(), Some(expected));
+            assert_eq!(split[1].next(), Some(vec![&62]));
+            let below_last = data.iter().filter(|&&i| i != 62).collect();
+            assert_eq!(split[2].next_back(), Some(below_last));
+            assert_eq!(split[3].next_back(), Some(data.iter().collect()));
+        }
+    }
+
+    #[test]
+    fn layers_cover_everything_once() {
+        let data: Vec<usize> = (0..7).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let constrained = generator.clone().with_required(&[2]).with_forbidden(&[5]);
+            for sg in &[generator, constrained] {
+                let mut expected: Vec<_> = sg.iter().collect();
+                expected.sort();
+                for chunks in 1..20 {
+                    let split = sg.split_by_layer(chunks);
+                    assert_eq!(split.len(), chunks);
+                    let mut union: Vec<_> = split.into_iter().flatten().collect();
+                    union.sort();
+                    assert_eq!(union, expected);
+                }
+            }
+        }
+
+        let empty: Vec<i32> = vec![];
+        let generator = SubsetGenerator::new(&empty, true);
+        let subsets: Vec<_> = generator.split_by_layer(3).into_iter().flatten().collect();
+        assert_eq!(subsets, vec![Vec::<&i32>::new()]);
+    }
+
+    #[test]
+    fn layers_are_balanced() {
+        let data: Vec<usize> = (0..10).collect();
+        let generator = SubsetGenerator::new(&data, true);
+        for chunks in 2..8 {
+            let mut counts = vec![vec![0usize; chunks]; data.len() + 1];
+            for (i, chunk) in generator.split_by_layer(chunks).into_iter().enumerate() {
+                let sizes: Vec<usize> = chunk.map(|s| s.len()).collect();
+                assert!(sizes.windows(2).all(|w| w[0] <= w[1]));
+                for size in sizes {
+                    counts[size][i] += 1;
+                }
+            }
+            for layer in counts {
+                let min = layer.iter().min().unwrap();
+                let max = layer.iter().max().unwrap();
+                assert!(max - min <= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn layer_chunks_skip_other_shares() {
+        let data: Vec<usize> = (0..12).collect();
+        let generator = SubsetGenerator::new(&data, true).with_forbidden(&[4]);
+        for chunks in 1..9 {
+            for mut chunk in generator.split_by_layer(chunks) {
+                let reported = chunk.by_ref().count();
+                assert_eq!(chunk.visited, reported);
+            }
+        }
+
+        // Every layer of 100 elements is far too large to step through.
+        let data: Vec<usize> = (0..100).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        let chunk = generator.split_by_layer(3).pop().unwrap();
+        let sizes: Vec<usize> = chunk.take(40).map(|s| s.len()).collect();
+        assert_eq!(sizes[..33], [1; 33]);
+        assert_eq!(sizes[33..], [2; 7]);
+        let mut chunk = generator.split_by_layer(1 << 10).pop().unwrap();
+        let first: Vec<usize> = chunk.next().unwrap().into_iter().copied().collect();
+        assert_eq!(first.len(), 2);
+        assert_eq!(chunk.visited, 1);
+    }
+
+    #[test]
+    fn unrank_layer_matches_stepping() {
+        for len in 0..9 {
+            for ones in 0..=len {
+                let mut mask = Mask::from_index(0, len);
+                let size = binomial(len, ones).unwrap();
+                for position in 0..size {
+                    assert!(mask.next_with_ones(ones) || ones == 0);
+                    let set = unrank_layer(position, ones, len, size);
+                    assert_eq!(set, mask.to_bitvec());
+                }
+            }
+        }
+        assert_eq!(binomial(100, 50), Some(100891344545564193334812497256));
+        assert_eq!(binomial(200, 100), None);
+    }
+
+    #[test]
+    fn chunks_on_threads() {
+        let data: Vec<u64> = (0..10).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        let total: u64 = std::thread::scope(|scope| {
+            let handles: Vec<_> = generator
+                .split(4)
+                .into_iter()
+                .map(|chunk| scope.spawn(move || chunk.map(|s| s.len() as u64).sum::<u64>()))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).sum()
+        });
+        assert_eq!(total, 10 * (1 << 9));
+    }
+}
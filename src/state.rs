@@ -0,0 +1,304 @@
+use crate::mask::Mask;
+use crate::{SubsetError, SubsetGenerator, SubsetIter};
+use bit_vec::BitVec;
+use core::cmp::Ordering;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A snapshot of the position of a [`SubsetIter`], created by
+/// [`SubsetIter::save_state`]. A new iterator can continue from the snapshot
+/// through [`SubsetGenerator::resume`]. With the `serde` feature the state can
+/// be serialized, so long-running enumerations can survive restarts.
+///
+/// States are ordered by their position in the enumeration: a state compares
+/// less than another if it has reported fewer subsets from the front, that is,
+/// if its cursor holds a smaller index. This makes it possible to keep pending
+/// checkpoints in a sorted set, or to check that one state precedes another.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// let mut iter = sg.iter();
+/// let before = iter.save_state();
+/// iter.next();
+/// assert!(before < iter.save_state());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SubsetState {
+    set: BitVec,
+    back: BitVec,
+    with_emptyset: bool,
+}
+
+/// Compares the front cursors as integers, then puts a pending base subset
+/// first, and finally compares the back cursors as integers.
+impl Ord for SubsetState {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_index(&self.set, &other.set)
+            .then_with(|| other.with_emptyset.cmp(&self.with_emptyset))
+            .then_with(|| compare_index(&self.back, &other.back))
+    }
+}
+
+impl PartialOrd for SubsetState {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compares the indices represented by two masks, where bit `i` is the `2^i`
+/// place. Masks over datasets of different sizes are ordered by their length.
+fn compare_index(a: &BitVec, b: &BitVec) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| {
+        (0..a.len())
+            .rev()
+            .map(|i| a[i].cmp(&b[i]))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    })
+}
+
+impl<'a, T> SubsetIter<'a, T> {
+    /// Returns a snapshot of the current position of the iterator. An iterator
+    /// resumed from the snapshot reports exactly the subsets this iterator has
+    /// not reported yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mut iter = sg.iter();
+    /// iter.next();
+    /// iter.next();
+    ///
+    /// let state = iter.save_state();
+    /// assert!(sg.resume(state).eq(iter));
+    /// ```
+    pub fn save_state(&self) -> SubsetState {
+        SubsetState {
+            set: self.set.to_bitvec(),
+            back: self.back.to_bitvec(),
+            with_emptyset: self.with_emptyset,
+        }
+    }
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator that continues from a snapshot taken with
+    /// [`SubsetIter::save_state`], without duplicating or skipping any subset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the snapshot was taken over a dataset of a different size, or
+    /// if its front cursor is past its back cursor, which no iterator produces
+    /// but a corrupted or hand-edited serialized state may contain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, true);
+    /// let mut iter = sg.iter();
+    /// iter.next();
+    /// let state = iter.save_state();
+    ///
+    /// let mut resumed = sg.resume(state);
+    /// assert_eq!(resumed.next(), Some(vec![&1]));
+    /// assert_eq!(resumed.count(), 6);
+    /// ```
+    pub fn resume(&self, state: SubsetState) -> SubsetIter<'a, T> {
+        assert!(
+            state.set.len() == self.free_len() && state.back.len() == self.free_len(),
+            "the state was saved over a dataset of a different size"
+        );
+        assert!(
+            compare_index(&state.set, &state.back).is_le(),
+            "the front cursor of the state is past its back cursor"
+        );
+        SubsetIter {
+            data: self.data,
+            set: Mask::from_bitvec(state.set),
+            back: Mask::from_bitvec(state.back),
+            with_emptyset: state.with_emptyset,
+            emptyset: self.includes_base(),
+            constraints: self.constraints.clone(),
+            exhausted: false,
+            reported: false,
+        }
+    }
+
+    /// Returns an iterator positioned just after the subset with the given
+    /// mask, as if that subset had been reported last: the next item is the
+    /// subset that follows it in the order of `iter`. Bit `i` of the mask
+    /// selects the `i`-th element, as in
+    /// [`current_mask`](SubsetIter::current_mask); with constraints, the bits
+    /// only span the free elements. This complements the index-based
+    /// [`seek`](SubsetIter::seek) for callers that persist the raw mask, which
+    /// also works for datasets of more than 64 elements. The empty set is not
+    /// reported again, as it precedes every other subset.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SubsetError::MaskLength`] if the mask does not have one bit
+    /// per (free) element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_vec::BitVec;
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, true);
+    ///
+    /// let mut mask = BitVec::from_elem(3, false);
+    /// mask.set(1, true);
+    /// let mut iter = sg.resume_from(mask).unwrap();
+    /// assert_eq!(iter.next(), Some(vec![&1, &2]));
+    /// assert_eq!(iter.count(), 4);
+    ///
+    /// assert!(sg.resume_from(BitVec::from_elem(4, false)).is_err());
+    /// ```
+    pub fn resume_from(&self, mask: BitVec) -> Result<SubsetIter<'a, T>, SubsetError> {
+        let len = self.free_len();
+        if mask.len() != len {
+            return Err(SubsetError::MaskLength {
+                len: mask.len(),
+                expected: len,
+            });
+        }
+        Ok(self.range_of(Mask::from_bitvec(mask), Mask::ones(len), false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SubsetState;
+    use crate::{SubsetError, SubsetGenerator};
+    use bit_vec::BitVec;
+
+    #[test]
+    fn resume_at_every_position() {
+        let data: Vec<usize> = (0..6).collect();
+        let generator = SubsetGenerator::new(&data, true);
+        for position in 0..=64 {
+            let mut iter = generator.iter();
+            let mut seen: Vec<_> = iter.by_ref().take(position).collect();
+            let state = iter.save_state();
+            seen.extend(generator.resume(state));
+            assert_eq!(seen, generator.iter().collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn resume_keeps_back() {
+        let data = vec![1, 2, 3];
+        let generator = SubsetGenerator::new(&data, false);
+        let mut iter = generator.iter();
+        iter.next();
+        iter.next_back();
+        let resumed = generator.resume(iter.save_state());
+        assert!(resumed.eq(iter));
+    }
+
+    #[test]
+    fn states_increase() {
+        for &n in &[5, 70] {
+            let data: Vec<usize> = (0..n).collect();
+            let generator = SubsetGenerator::new(&data, true);
+            let mut iter = generator.iter();
+            let mut previous = iter.save_state();
+            while iter.next().is_some() {
+                let state = iter.save_state();
+                assert!(previous < state);
+                assert_eq!(state, generator.resume(state.clone()).save_state());
+                previous = state;
+                if iter.current_index() == 40 {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn resume_from_every_mask() {
+        for &n in &[0, 6, 70] {
+            let data: Vec<usize> = (0..n).collect();
+            for &with_emptyset in &[false, true] {
+                let generator = SubsetGenerator::new(&data, with_emptyset);
+                let mut iter = generator.iter();
+                while iter.next().is_some() {
+                    let mut resumed = generator.resume_from(iter.current_mask()).unwrap();
+                    assert!(resumed.by_ref().take(3).eq(iter.clone().take(3)));
+                    if iter.current_index() == 40 {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let data: Vec<usize> = (0..5).collect();
+        let generator = SubsetGenerator::new(&data, false).with_required(&[1]);
+        let mut iter = generator.iter();
+        iter.nth(4);
+        let resumed = generator.resume_from(iter.current_mask()).unwrap();
+        assert!(resumed.eq(iter));
+    }
+
+    #[test]
+    fn resume_from_wrong_length() {
+        let data = vec![1, 2, 3];
+        let generator = SubsetGenerator::new(&data, false).with_required(&[0]);
+        let result = generator.resume_from(BitVec::from_elem(3, false));
+        assert_eq!(
+            result.err(),
+            Some(SubsetError::MaskLength {
+                len: 3,
+                expected: 2
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn resume_wrong_size() {
+        let data = vec![1, 2, 3];
+        let state = SubsetGenerator::new(&data, false).iter().save_state();
+        SubsetGenerator::new(&data[1..], false).resume(state);
+    }
+
+    #[test]
+    #[should_panic(expected = "past its back cursor")]
+    fn resume_crossed_cursors() {
+        let data = vec![1, 2, 3];
+        let state = SubsetState {
+            set: BitVec::from_elem(3, true),
+            back: BitVec::from_elem(3, false),
+            with_emptyset: false,
+        };
+        SubsetGenerator::new(&data, false).resume(state);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let data: Vec<usize> = (0..10).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        let mut iter = generator.iter();
+        iter.nth(100);
+
+        let json = serde_json::to_string(&iter.save_state()).unwrap();
+        let state = serde_json::from_str(&json).unwrap();
+        assert!(generator.resume(state).eq(iter));
+    }
+}
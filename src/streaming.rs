@@ -0,0 +1,177 @@
+use crate::{SubsetGenerator, SubsetIter};
+use alloc::vec::Vec;
+#[cfg(feature = "streaming-iterator")]
+use streaming_iterator::StreamingIterator;
+
+/// Lending iterator over all the subsets, created by
+/// [`SubsetGenerator::streaming_iter`]. Every subset is written into a buffer
+/// owned by the iterator and lent out as a slice, so the enumeration performs
+/// no per-subset allocations. Unlike with
+/// [`next_into`](SubsetIter::next_into), the borrow checker enforces that a
+/// subset is no longer used once the iterator advances.
+///
+/// `advance` moves to the next subset, and `get` returns the current one.
+/// With the `streaming-iterator` feature, the iterator implements the
+/// `StreamingIterator` trait of the `streaming-iterator` crate, which adds
+/// `next` and adapters such as `filter` and `for_each` on top of these.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// let mut stream = sg.streaming_iter();
+/// let mut total = 0;
+/// stream.advance();
+/// while let Some(subset) = stream.get() {
+///     total += subset.iter().copied().sum::<i32>();
+///     stream.advance();
+/// }
+/// assert_eq!(total, 24);
+/// ```
+pub struct StreamingSubsetIter<'a, T> {
+    iter: SubsetIter<'a, T>,
+    buf: Vec<&'a T>,
+    valid: bool,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns a lending iterator over all the subsets, in the order of
+    /// `iter`, which lends each subset as a slice of an internal buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2];
+    /// let sg = SubsetGenerator::new(&data, true);
+    /// let mut stream = sg.streaming_iter();
+    ///
+    /// assert_eq!(stream.get(), None);
+    /// stream.advance();
+    /// assert_eq!(stream.get(), Some(&[][..]));
+    /// stream.advance();
+    /// assert_eq!(stream.get(), Some(&[&1][..]));
+    /// ```
+    pub fn streaming_iter(&self) -> StreamingSubsetIter<'a, T> {
+        StreamingSubsetIter {
+            iter: self.iter(),
+            buf: Vec::with_capacity(self.data.len()),
+            valid: false,
+        }
+    }
+}
+
+impl<'a, T> StreamingSubsetIter<'a, T> {
+    /// Moves to the next subset. Once the subsets are exhausted, `get`
+    /// returns `None`.
+    pub fn advance(&mut self) {
+        self.valid = self.iter.next_into(&mut self.buf);
+    }
+
+    /// Returns the current subset, or `None` before the first call to
+    /// `advance` and after the subsets are exhausted.
+    pub fn get(&self) -> Option<&[&'a T]> {
+        if self.valid {
+            Some(&self.buf)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the bounds on the number of remaining subsets, like
+    /// `Iterator::size_hint`.
+    pub fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Forwards to the inherent [`advance`](StreamingSubsetIter::advance) and
+/// [`get`](StreamingSubsetIter::get). Only available with the
+/// `streaming-iterator` feature.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "streaming-iterator")]
+/// # {
+/// use streaming_iterator::StreamingIterator;
+/// use subset_generator::SubsetGenerator;
+///
+/// let data = vec![1, 2, 3];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// let mut pairs = sg.streaming_iter().filter(|s| s.len() == 2);
+/// assert_eq!(pairs.next(), Some(&[&1, &2][..]));
+/// assert_eq!(pairs.count(), 2);
+/// # }
+/// ```
+#[cfg(feature = "streaming-iterator")]
+impl<'a, T> StreamingIterator for StreamingSubsetIter<'a, T> {
+    type Item = [&'a T];
+
+    fn advance(&mut self) {
+        StreamingSubsetIter::advance(self);
+    }
+
+    fn get(&self) -> Option<&Self::Item> {
+        StreamingSubsetIter::get(self)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        StreamingSubsetIter::size_hint(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+
+    #[test]
+    fn streaming_matches_iter() {
+        let data: Vec<usize> = (0..6).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset).with_required(&[1]);
+            let mut stream = generator.streaming_iter();
+            assert_eq!(stream.get(), None);
+            let mut expected = generator.iter();
+            stream.advance();
+            while let Some(subset) = stream.get() {
+                assert_eq!(Some(subset.to_vec()), expected.next());
+                assert_eq!(stream.size_hint(), expected.size_hint());
+                stream.advance();
+            }
+            assert_eq!(expected.next(), None);
+            assert_eq!(stream.get(), None);
+        }
+    }
+
+    #[cfg(feature = "streaming-iterator")]
+    #[test]
+    fn streaming_iterator_trait() {
+        use streaming_iterator::StreamingIterator;
+
+        let data: Vec<u32> = (0..6).collect();
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset).with_forbidden(&[2]);
+            let mut stream = generator.streaming_iter();
+            let mut expected = generator.iter();
+            while let Some(subset) = StreamingIterator::next(&mut stream) {
+                assert_eq!(Some(subset.to_vec()), expected.next());
+            }
+            assert_eq!(expected.next(), None);
+
+            let even = generator
+                .streaming_iter()
+                .filter(|s| s.iter().copied().sum::<u32>() % 2 == 0)
+                .count();
+            assert_eq!(
+                even as u64,
+                generator.count_matching(|s| s.iter().copied().sum::<u32>() % 2 == 0)
+            );
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use crate::constraints::FreeElements;
+use crate::SubsetGenerator;
+use alloc::vec::Vec;
+use bit_vec::BitVec;
+
+/// Iterator over the subsets contained in a given subset, created by
+/// [`SubsetGenerator::submasks_of`].
+///
+/// # Examples
+///
+/// ```
+/// use bit_vec::BitVec;
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![1, 2, 3, 4];
+/// let sg = SubsetGenerator::new(&data, true);
+///
+/// let mask = BitVec::from_fn(4, |i| i % 2 == 0);
+/// assert_eq!(sg.submasks_of(&mask).count(), 4);
+/// ```
+pub struct SubmaskIter<'a, T> {
+    elements: FreeElements<'a, T>,
+    sub: BitVec,
+    sup: BitVec,
+    first: bool,
+    exhausted: bool,
+    with_emptyset: bool,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over the subsets of the subset described by `mask`,
+    /// where bit `i` of the mask selects the `i`-th element of the dataset. The
+    /// submasks are enumerated with the classic `sub = (sub - 1) & mask` trick,
+    /// in decreasing order starting from `mask` itself, so only the
+    /// `2^popcount(mask)` submasks are visited instead of all `2^n` masks. The
+    /// empty set comes last, and is reported if (and only if) the generator was
+    /// configured to include the empty set.
+    ///
+    /// The constraints of the generator are respected: the submasks only vary
+    /// the free elements of `mask`, in the order set by
+    /// [`with_order`](Self::with_order), and contain all the required
+    /// elements. If `mask` misses a required element, there are none.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `mask` differs from the size of the dataset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_vec::BitVec;
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let sg = SubsetGenerator::new(&data, false);
+    /// let mask = BitVec::from_fn(3, |i| i != 1);
+    /// let mut iter = sg.submasks_of(&mask);
+    ///
+    /// assert_eq!(iter.next(), Some(vec![&1, &3]));
+    /// assert_eq!(iter.next(), Some(vec![&3]));
+    /// assert_eq!(iter.next(), Some(vec![&1]));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn submasks_of(&self, mask: &BitVec) -> SubmaskIter<'a, T> {
+        assert_eq!(
+            mask.len(),
+            self.data.len(),
+            "the mask must have one bit per element"
+        );
+        let elements = self.free_elements();
+        let sup = BitVec::from_fn(elements.len(), |j| mask[elements.index(j)]);
+        let exhausted = elements.required().iter().any(|&i| !mask[i]);
+        SubmaskIter {
+            elements,
+            sub: sup.clone(),
+            sup,
+            first: true,
+            exhausted,
+            with_emptyset: self.includes_base(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for SubmaskIter<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+
+        if self.first {
+            self.first = false;
+        } else {
+            // Subtracting 1 clears the lowest set bit and sets all bits below
+            // it, of which only the ones inside the mask are kept.
+            for i in 0..self.sub.len() {
+                if self.sub[i] {
+                    self.sub.set(i, false);
+                    break;
+                }
+                self.sub.set(i, true);
+            }
+            self.sub.and(&self.sup);
+        }
+
+        if self.sub.none() {
+            self.exhausted = true;
+            return if self.with_emptyset {
+                Some(self.elements.collect(None))
+            } else {
+                None
+            };
+        }
+        Some(self.elements.subset(&self.sub))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+    use bit_vec::BitVec;
+
+    #[test]
+    fn submask_count() {
+        let data: Vec<usize> = (0..10).collect();
+        let generator = SubsetGenerator::new(&data, true);
+        for pattern in &[0u32, 1, 0b1011, 0b11_0101_1010, 0b11_1111_1111] {
+            let mask = BitVec::from_fn(10, |i| pattern & (1 << i) != 0);
+            let submasks: Vec<_> = generator.submasks_of(&mask).collect();
+            assert_eq!(submasks.len(), 1 << pattern.count_ones());
+            for subset in &submasks {
+                assert!(subset.iter().all(|&&i| mask[i]));
+            }
+            let mut distinct = submasks.clone();
+            distinct.sort();
+            distinct.dedup();
+            assert_eq!(distinct.len(), submasks.len());
+        }
+    }
+
+    #[test]
+    fn submask_without_empty() {
+        let data = vec![1, 2, 3, 4];
+        let generator = SubsetGenerator::new(&data, false);
+        let mask = BitVec::from_elem(4, false);
+        assert_eq!(generator.submasks_of(&mask).count(), 0);
+        let mask = BitVec::from_elem(4, true);
+        assert_eq!(generator.submasks_of(&mask).count(), 15);
+    }
+
+    #[test]
+    #[should_panic]
+    fn submask_wrong_length() {
+        let data = vec![1, 2, 3];
+        let generator = SubsetGenerator::new(&data, false);
+        generator.submasks_of(&BitVec::from_elem(4, true));
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{Prune, SubsetGenerator};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// The largest dataset with nonnegative values that `subset_sum` searches
+/// depth-first; beyond it, meet-in-the-middle is used.
+const PRUNE_MAX_LEN: usize = 24;
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns a subset whose elements have values summing to exactly
+    /// `target`, or `None` if there is none. This solves the
+    /// [Subset Sum problem](https://en.wikipedia.org/wiki/Subset_sum_problem)
+    /// with the best of the specialized searches for the input:
+    ///
+    /// - If all values are nonnegative and at most 24 elements are free, the
+    ///   subsets are searched with [`prune_iter`](Self::prune_iter), skipping
+    ///   the extensions of every subset whose sum already exceeds `target`.
+    /// - Otherwise, [`meet_in_middle`](Self::meet_in_middle) is used, which
+    ///   takes `O(2^(n/2))` memory, but handles negative values and needs far
+    ///   fewer steps than a full enumeration for large datasets.
+    ///
+    /// The sums are computed without overflow. The empty set, whose sum is 0,
+    /// is only returned if the generator includes it. Both searches respect
+    /// the constraints of the generator, and the size limit of the first one
+    /// only counts the free elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![3, 34, 4, 12, 5, 2];
+    /// let sg = SubsetGenerator::new(&data, false);
+    ///
+    /// let found = sg.subset_sum(9, |&x| x).unwrap();
+    /// assert_eq!(found.into_iter().sum::<i64>(), 9);
+    /// assert_eq!(sg.subset_sum(30, |&x| x), None);
+    /// ```
+    pub fn subset_sum<F>(&self, target: i64, value: F) -> Option<Vec<&'a T>>
+    where
+        F: Fn(&T) -> i64,
+    {
+        let target = target as i128;
+        let sum = |s: &[&T]| s.iter().map(|&x| value(x) as i128).sum::<i128>();
+
+        let nonnegative = self.data.iter().all(|x| value(x) >= 0);
+        if nonnegative && self.free_len() <= PRUNE_MAX_LEN {
+            let mut iter = self.prune_iter(|s| match sum(s).cmp(&target) {
+                Ordering::Equal => Prune::Keep,
+                Ordering::Less => Prune::Reject,
+                Ordering::Greater => Prune::RejectSupersets,
+            });
+            iter.next()
+        } else {
+            self.meet_in_middle(sum, |a, b| (a + b).cmp(&target))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+
+    /// Checks `subset_sum` against a full enumeration for every target in the
+    /// range of possible sums.
+    fn check(data: &[i64]) {
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(data, with_emptyset);
+            let low: i64 = data.iter().filter(|&&x| x < 0).sum();
+            let high: i64 = data.iter().filter(|&&x| x > 0).sum();
+            for target in low - 2..=high + 2 {
+                let found = generator.subset_sum(target, |&x| x);
+                let exists = generator
+                    .iter()
+                    .any(|s| s.into_iter().sum::<i64>() == target);
+                assert_eq!(found.is_some(), exists, "{:?} {}", data, target);
+                if let Some(subset) = found {
+                    assert_eq!(subset.iter().copied().sum::<i64>(), target);
+                    assert!(with_emptyset || !subset.is_empty());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn subset_sum_positive() {
+        check(&[3, 34, 4, 12, 5, 2]);
+        check(&[0, 7, 7, 1]);
+    }
+
+    #[test]
+    fn subset_sum_negative() {
+        check(&[-3, -8, -1, -12]);
+    }
+
+    #[test]
+    fn subset_sum_mixed() {
+        check(&[5, -7, 3, -2, 9, -4, 1]);
+    }
+
+    #[test]
+    fn subset_sum_large() {
+        // Too large to prune, so this goes through meet-in-the-middle.
+        let data: Vec<u64> = (0..30).map(|i| 1 << i).collect();
+        let generator = SubsetGenerator::new(&data, false);
+        let found = generator
+            .subset_sum(0b1010_0000_0001, |&x| x as i64)
+            .unwrap();
+        assert_eq!(found, vec![&1, &(1 << 9), &(1 << 11)]);
+        assert_eq!(generator.subset_sum(1 << 30, |&x| x as i64), None);
+        let found = generator.subset_sum(i64::MAX, |_| i64::MAX);
+        assert_eq!(found.map(|s| s.len()), Some(1));
+    }
+}
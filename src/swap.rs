@@ -0,0 +1,147 @@
+use crate::constraints::FreeElements;
+use crate::KSubsetIter;
+use alloc::vec::Vec;
+
+/// Iterator over the ways to swap elements out of a solution, created by
+/// [`swap_iter`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::swap_iter;
+///
+/// // Remove one of 2 elements and add at most one of 3 candidates.
+/// assert_eq!(swap_iter(&[0, 1], &[2, 3, 4], 1).count(), 2 * (1 + 3));
+/// ```
+pub struct SwapIter<'a> {
+    current: &'a [usize],
+    candidates: &'a [usize],
+    max_swap: usize,
+    removed: usize,
+    added: usize,
+    remove: KSubsetIter<'a, usize>,
+    remove_set: Option<Vec<usize>>,
+    add: KSubsetIter<'a, usize>,
+}
+
+/// Returns an iterator over the pairs `(remove, add)`, where `remove` is a set
+/// of at most `max_swap` indices from `current`, and `add` is a set of at most
+/// as many indices from `candidates`. This enumerates the local modifications
+/// of a solution in the compression step of iterative compression, where the
+/// new solution must not be larger than the old one. The number of pairs is
+/// polynomial in the input for a fixed `max_swap`.
+///
+/// The pairs are reported in increasing number of removed elements, then in
+/// increasing number of added elements, and the sets themselves in the order
+/// of [`combinations`](crate::SubsetGenerator::combinations). The trivial
+/// pair of two empty sets is not reported. The indices are copied from the
+/// slices, which are not required to be sorted or disjoint.
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::swap_iter;
+///
+/// let mut iter = swap_iter(&[0, 1], &[5], 1);
+///
+/// assert_eq!(iter.next(), Some((vec![0], vec![])));
+/// assert_eq!(iter.next(), Some((vec![1], vec![])));
+/// assert_eq!(iter.next(), Some((vec![0], vec![5])));
+/// assert_eq!(iter.next(), Some((vec![1], vec![5])));
+/// assert_eq!(iter.next(), None);
+/// ```
+pub fn swap_iter<'a>(
+    current: &'a [usize],
+    candidates: &'a [usize],
+    max_swap: usize,
+) -> SwapIter<'a> {
+    SwapIter {
+        current,
+        candidates,
+        max_swap,
+        removed: 1,
+        added: 0,
+        remove: KSubsetIter::new(FreeElements::new(current), 1),
+        remove_set: None,
+        add: KSubsetIter::new(FreeElements::new(candidates), 0),
+    }
+}
+
+impl<'a> SwapIter<'a> {
+    /// Moves to the next pair of sizes of the removed and added sets. Returns
+    /// false once all the pairs of sizes have been exhausted.
+    fn next_sizes(&mut self) -> bool {
+        if self.added < self.removed.min(self.candidates.len()) {
+            self.added += 1;
+        } else if self.removed < self.max_swap.min(self.current.len()) {
+            self.removed += 1;
+            self.added = 0;
+        } else {
+            return false;
+        }
+        self.remove = KSubsetIter::new(FreeElements::new(self.current), self.removed);
+        self.remove_set = None;
+        true
+    }
+}
+
+impl<'a> Iterator for SwapIter<'a> {
+    type Item = (Vec<usize>, Vec<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.max_swap == 0 {
+            return None;
+        }
+        loop {
+            if let Some(remove) = &self.remove_set {
+                if let Some(add) = self.add.next() {
+                    return Some((remove.clone(), add.into_iter().copied().collect()));
+                }
+            }
+            if let Some(remove) = self.remove.next() {
+                self.remove_set = Some(remove.into_iter().copied().collect());
+                self.add = KSubsetIter::new(FreeElements::new(self.candidates), self.added);
+            } else if !self.next_sizes() {
+                return None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::swap_iter;
+
+    fn binomial(n: usize, k: usize) -> usize {
+        (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+    }
+
+    #[test]
+    fn swap_counts() {
+        let current = [1, 4, 6, 7];
+        let candidates = [0, 2, 3, 5, 8];
+        for max_swap in 0..6 {
+            let pairs: Vec<_> = swap_iter(&current, &candidates, max_swap).collect();
+            let mut expected = 0;
+            for removed in 1..=max_swap.min(4) {
+                for added in 0..=removed {
+                    expected += binomial(4, removed) * binomial(5, added);
+                }
+            }
+            assert_eq!(pairs.len(), expected);
+            for (remove, add) in pairs {
+                assert!(!remove.is_empty() && remove.len() <= max_swap);
+                assert!(add.len() <= remove.len());
+                assert!(remove.iter().all(|i| current.contains(i)));
+                assert!(add.iter().all(|i| candidates.contains(i)));
+            }
+        }
+    }
+
+    #[test]
+    fn swap_edge_cases() {
+        assert_eq!(swap_iter(&[], &[1, 2], 3).count(), 0);
+        assert_eq!(swap_iter(&[1, 2], &[], 3).count(), 3);
+        assert_eq!(swap_iter(&[1, 2], &[3], 0).count(), 0);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::constraints::FreeElements;
+use crate::SubsetGenerator;
+use alloc::collections::BinaryHeap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+
+/// Iterator over all the subsets in nondecreasing order of total weight,
+/// created by [`SubsetGenerator::by_weight`].
+///
+/// # Examples
+///
+/// ```
+/// use subset_generator::SubsetGenerator;
+/// let data = vec![4, 1, 3];
+/// let sg = SubsetGenerator::new(&data, false);
+///
+/// let sums: Vec<u64> = sg.by_weight(|&x| x).map(|s| s.into_iter().sum()).collect();
+/// assert_eq!(sums, vec![1, 3, 4, 4, 5, 7, 8]);
+/// ```
+pub struct WeightOrderedIter<'a, T> {
+    elements: FreeElements<'a, T>,
+    order: Vec<usize>,
+    weights: Vec<u64>,
+    heap: BinaryHeap<Reverse<(u128, Vec<usize>)>>,
+    with_emptyset: bool,
+}
+
+impl<'a, T> SubsetGenerator<'a, T> {
+    /// Returns an iterator over all the subsets in nondecreasing order of their
+    /// total weight, where the weight of a subset is the sum of `weight` over
+    /// its elements. This allows stopping at the first feasible subset when
+    /// looking for the cheapest one. Subsets of equal weight are reported in
+    /// an unspecified but deterministic order, and the elements of each subset
+    /// in the order of the dataset. The empty set, which has weight 0, comes
+    /// first if (and only if) the generator was configured to include it. The
+    /// constraints of the generator are respected: only the free elements are
+    /// ordered by weight, and the required elements, whose weight is the same
+    /// for all subsets, are added to every subset.
+    ///
+    /// The elements are sorted by weight, and the subsets are explored
+    /// best-first over the lattice of subsets: the successors of a subset whose
+    /// heaviest element is the `i`-th lightest either add the `i + 1`-th
+    /// lightest element, or replace the `i`-th by the `i + 1`-th. Each subset is
+    /// generated exactly once, and only the frontier is kept in a binary heap.
+    ///
+    /// Unlike the *O(n)* memory of `iter`, the frontier grows by at most one
+    /// subset per reported subset, so after `k` subsets the iterator holds
+    /// *O(k * n)* memory, and each step takes *O(n + log k)* time. This pays
+    /// off when only a few of the cheapest subsets are needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use subset_generator::SubsetGenerator;
+    ///
+    /// let data = vec![("a", 5), ("b", 2), ("c", 4)];
+    /// let sg = SubsetGenerator::new(&data, true);
+    /// let mut iter = sg.by_weight(|&(_, w)| w);
+    ///
+    /// assert_eq!(iter.next(), Some(vec![]));
+    /// assert_eq!(iter.next(), Some(vec![&("b", 2)]));
+    /// assert_eq!(iter.next(), Some(vec![&("c", 4)]));
+    /// assert_eq!(iter.next(), Some(vec![&("a", 5)]));
+    /// assert_eq!(iter.next(), Some(vec![&("b", 2), &("c", 4)]));
+    /// ```
+    pub fn by_weight<F>(&self, weight: F) -> WeightOrderedIter<'a, T>
+    where
+        F: Fn(&T) -> u64,
+    {
+        let elements = self.free_elements();
+        let mut order: Vec<usize> = (0..elements.len()).collect();
+        order.sort_by_key(|&j| weight(elements.get(j)));
+        let weights: Vec<u64> = order.iter().map(|&j| weight(elements.get(j))).collect();
+
+        let mut heap = BinaryHeap::new();
+        if let Some(&lightest) = weights.first() {
+            heap.push(Reverse((lightest as u128, vec![0])));
+        }
+        WeightOrderedIter {
+            elements,
+            order,
+            weights,
+            heap,
+            with_emptyset: self.includes_base(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for WeightOrderedIter<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.with_emptyset {
+            self.with_emptyset = false;
+            return Some(self.elements.collect(None));
+        }
+
+        let Reverse((total, positions)) = self.heap.pop()?;
+        let last = positions[positions.len() - 1];
+        if last + 1 < self.weights.len() {
+            let next = self.weights[last + 1] as u128;
+            let mut added = positions.clone();
+            added.push(last + 1);
+            self.heap.push(Reverse((total + next, added)));
+
+            let mut replaced = positions.clone();
+            *replaced.last_mut().unwrap() = last + 1;
+            let total = total - self.weights[last] as u128 + next;
+            self.heap.push(Reverse((total, replaced)));
+        }
+
+        let mut bits: Vec<usize> = positions.iter().map(|&p| self.order[p]).collect();
+        bits.sort_unstable();
+        Some(self.elements.collect(bits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SubsetGenerator;
+
+    #[test]
+    fn weights_nondecreasing() {
+        let data: Vec<u64> = vec![7, 3, 3, 10, 1, 6, 2, 9];
+        for &with_emptyset in &[false, true] {
+            let generator = SubsetGenerator::new(&data, with_emptyset);
+            let ordered: Vec<_> = generator.by_weight(|&x| x).collect();
+            let sums: Vec<u64> = ordered.iter().map(|s| s.iter().copied().sum()).collect();
+            assert!(sums.windows(2).all(|w| w[0] <= w[1]));
+
+            let mut ordered = ordered;
+            let mut expected: Vec<_> = generator.iter().collect();
+            ordered.sort();
+            expected.sort();
+            assert_eq!(ordered, expected);
+        }
+    }
+
+    #[test]
+    fn weights_near_overflow() {
+        let data = vec![u64::MAX, u64::MAX, 1];
+        let generator = SubsetGenerator::new(&data, false);
+        let last = generator.by_weight(|&x| x).last().unwrap();
+        assert_eq!(last.len(), 3);
+    }
+
+    #[test]
+    fn weights_empty_dataset() {
+        let data: Vec<u64> = vec![];
+        assert_eq!(
+            SubsetGenerator::new(&data, true).by_weight(|&x| x).count(),
+            1
+        );
+        assert_eq!(
+            SubsetGenerator::new(&data, false).by_weight(|&x| x).count(),
+            0
+        );
+    }
+}